@@ -0,0 +1,221 @@
+/* Write the worlds of tests/fixtures. Run `cargo run -p numcraft-format --example generate_fixtures` after changing
+the format, then check the diff of the fixtures before committing them. */
+
+use std::{fs, path::Path};
+
+use fastnoise_lite::FastNoiseLite;
+use lz4_flex::compress_prepend_size;
+use nalgebra::Vector3;
+use numcraft_format::{
+    format::{chunk::Chunk, save_manager::GameMode},
+    header::WorldInfo1,
+    numcraft_v0_1_0, numcraft_v0_1_3,
+};
+
+const SEED: i32 = 1234;
+
+/// The worlds written for each version, as (name, gamemode).
+const WORLDS: [(&str, GameMode); 5] = [
+    ("empty", GameMode::Survival),
+    ("built", GameMode::Survival),
+    ("survival", GameMode::Survival),
+    ("creative", GameMode::Creative),
+    ("max_inventory", GameMode::Survival),
+];
+
+/// Chunks of the terrain of the v0.1.0 generator, with a small house for the "built" world.
+fn make_chunks<B: numcraft_format::format::FormatBlock>(
+    kind: &str,
+    house: [B; 2],
+) -> Vec<Chunk<B>> {
+    let noise = FastNoiseLite::with_seed(SEED);
+    numcraft_format::dimensions::WorldDimensions::V0_1_X
+        .iter_chunks()
+        .map(|pos| {
+            let mut chunk = Chunk::new(pos);
+            if kind != "empty" {
+                chunk.generate_chunk(&noise);
+            }
+            if kind == "built" && pos == Vector3::new(1, 0, 1) {
+                for x in 1..6 {
+                    for z in 1..6 {
+                        for y in 0..4 {
+                            let wall = x == 1 || x == 5 || z == 1 || z == 5;
+                            let block = if y == 0 { house[1] } else { house[0] };
+                            if wall || y == 0 {
+                                chunk.set_at(Vector3::new(x, y, z), block);
+                            }
+                        }
+                    }
+                }
+            }
+            chunk
+        })
+        .collect()
+}
+
+/// The first air block above the ground of a v0.1.3 world, like the hills of the game: between 3 and 9 blocks high.
+fn get_ground_height(noise: &FastNoiseLite, x: isize, z: isize) -> isize {
+    ((noise.get_noise_2d(x as f32, z as f32) + 1.) / 2. * 6. + 3.) as isize
+}
+
+/// v0.1.3 worlds have their floor at y = 0, closed by the border on the sides and the floor. The "built" world has a
+/// small house standing on a foundation.
+fn make_v0_1_3_chunks(kind: &str) -> Vec<numcraft_v0_1_3::chunk::Chunk> {
+    use numcraft_v0_1_3::constants::BlockType;
+
+    let noise = FastNoiseLite::with_seed(SEED);
+    let size = (numcraft_format::format::CHUNK_SIZE * 4) as isize;
+    let house_floor = 10;
+
+    let get_block = |x: isize, y: isize, z: isize| {
+        if x == 0 || x == size - 1 || z == 0 || z == size - 1 || y == 0 {
+            return BlockType::Border;
+        }
+        let ground = if kind == "empty" {
+            1
+        } else {
+            get_ground_height(&noise, x, z)
+        };
+        let in_house = kind == "built" && (9..=13).contains(&x) && (9..=13).contains(&z);
+        if in_house {
+            let wall = x == 9 || x == 13 || z == 9 || z == 13;
+            if y < house_floor && y >= ground || y == house_floor {
+                return BlockType::Cobblestone;
+            }
+            if wall && y > house_floor && y < house_floor + 4 {
+                return BlockType::Planks;
+            }
+        }
+        match ground - y {
+            1 => BlockType::Grass,
+            2..=3 => BlockType::Dirt,
+            d if d > 3 => BlockType::Stone,
+            _ => BlockType::Air,
+        }
+    };
+
+    numcraft_format::dimensions::WorldDimensions::V0_1_X
+        .iter_chunks()
+        .map(|pos| {
+            let mut chunk = Chunk::new(pos);
+            let chunk_size = numcraft_format::format::CHUNK_SIZE;
+            for x in 0..chunk_size {
+                for y in 0..chunk_size {
+                    for z in 0..chunk_size {
+                        let world_pos =
+                            pos * chunk_size as isize + Vector3::new(x, y, z).map(|c| c as isize);
+                        chunk.set_at(
+                            Vector3::new(x, y, z),
+                            get_block(world_pos.x, world_pos.y, world_pos.z),
+                        );
+                    }
+                }
+            }
+            chunk
+        })
+        .collect()
+}
+
+fn make_v0_1_3(kind: &str, gamemode: GameMode) -> Vec<u8> {
+    use numcraft_v0_1_3::{constants::ItemType, inventory::ItemStack, save_manager::SaveManager};
+
+    let mut save_manager = SaveManager::new();
+    save_manager.set_world_name(&format!("v0.1.3 {kind}"));
+    save_manager.set_world_seed(SEED);
+    save_manager.set_gamemode(gamemode);
+    // The player stands on the ground
+    let ground = if kind == "empty" {
+        1
+    } else {
+        get_ground_height(&FastNoiseLite::with_seed(SEED), 14, 14)
+    };
+    save_manager.player_data.pos = (14.5, ground as f32, 14.5);
+    save_manager.player_data.rotation = (0.25, 1.5);
+
+    for chunk in make_v0_1_3_chunks(kind) {
+        save_manager.set_chunk(&chunk);
+    }
+
+    let inventory = &mut save_manager.player_data.inventory;
+    let slots = inventory.get_all_slots().len();
+    for slot in 0..slots {
+        let item_type = ItemType::get_from_id((slot % 9 + 1) as u8).unwrap();
+        let item_stack = match kind {
+            "survival" if slot < 6 => ItemStack::new(item_type, (slot * 7 + 3) as u8, false),
+            "creative" if slot < 9 => ItemStack::new(item_type, 0, true),
+            "max_inventory" => ItemStack::new(item_type, item_type.get_max_stack_amount(), false),
+            _ => continue,
+        };
+        inventory.replace_slot_item_stack(slot, item_stack);
+    }
+
+    save_manager.get_raw()
+}
+
+fn make_v0_1_0(kind: &str, gamemode: GameMode) -> Vec<u8> {
+    use numcraft_v0_1_0::{
+        constants::{BlockType, ItemType},
+        inventory::{Inventory, ItemStack},
+        save_manager::SaveManager,
+    };
+
+    let mut save_manager = SaveManager::new();
+    save_manager.world_info.world_name = format!("v0.1.0 {kind}");
+    save_manager.world_info.world_seed = SEED;
+    save_manager.world_info.gamemode = gamemode;
+    save_manager.player_data.pos = (14.5, 4., 14.5);
+    save_manager.player_data.rotation = (0.25, 1.5);
+    save_manager.player_data.inventory = Inventory::new(24);
+
+    for chunk in make_chunks(kind, [BlockType::Stone, BlockType::Dirt]) {
+        save_manager.set_chunk(&chunk);
+    }
+
+    let inventory = &mut save_manager.player_data.inventory;
+    for slot in 0..inventory.get_all_slots().len() {
+        let item_type = ItemType::get_from_id((slot % 3 + 1) as u8).unwrap();
+        let item_stack = match kind {
+            "survival" if slot < 6 => ItemStack::new(item_type, (slot * 7 + 3) as u8, false),
+            "creative" if slot < 3 => ItemStack::new(item_type, 0, true),
+            "max_inventory" => ItemStack::new(item_type, item_type.get_max_stack_amount(), false),
+            _ => continue,
+        };
+        inventory.replace_slot_item_stack(slot, item_stack);
+    }
+
+    save_manager.get_raw()
+}
+
+/// Only the header of the v0.0.7 - v0.0.9 worlds is read by the site.
+fn make_v0_0_7() -> Vec<u8> {
+    let header = postcard::to_allocvec(&WorldInfo1 {
+        world_name: "v0.0.7 world".to_string(),
+        world_seed: SEED,
+    })
+    .unwrap();
+
+    let mut data = (header.len() as u16).to_be_bytes().to_vec();
+    data.extend(header);
+    data.extend(compress_prepend_size(&[]));
+    data
+}
+
+fn main() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    fs::create_dir_all(&fixtures).unwrap();
+
+    fs::write(fixtures.join("v0_0_7_header.ncw"), make_v0_0_7()).unwrap();
+    for (kind, gamemode) in WORLDS {
+        fs::write(
+            fixtures.join(format!("v0_1_0_{kind}.ncw")),
+            make_v0_1_0(kind, gamemode),
+        )
+        .unwrap();
+        fs::write(
+            fixtures.join(format!("v0_1_3_{kind}.ncw")),
+            make_v0_1_3(kind, gamemode),
+        )
+        .unwrap();
+    }
+}
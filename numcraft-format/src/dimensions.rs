@@ -0,0 +1,56 @@
+use nalgebra::Vector3;
+
+/// The size of a world, in chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldDimensions {
+    pub x: usize,
+    pub y: usize,
+    pub z: usize,
+}
+
+impl WorldDimensions {
+    /// Every v0.1.x world is 4 x 4 x 4 chunks.
+    pub const V0_1_X: WorldDimensions = WorldDimensions::new(4, 4, 4);
+
+    pub const fn new(x: usize, y: usize, z: usize) -> Self {
+        WorldDimensions { x, y, z }
+    }
+
+    pub const fn get_chunk_count(&self) -> usize {
+        self.x * self.y * self.z
+    }
+
+    pub fn contains_chunk(&self, pos: Vector3<isize>) -> bool {
+        pos.x >= 0
+            && pos.y >= 0
+            && pos.z >= 0
+            && (pos.x as usize) < self.x
+            && (pos.y as usize) < self.y
+            && (pos.z as usize) < self.z
+    }
+
+    /// Index of the chunk in the save file, or None if the chunk is outside of the world.
+    pub fn get_chunk_index(&self, pos: Vector3<isize>) -> Option<usize> {
+        if self.contains_chunk(pos) {
+            Some(pos.x as usize + pos.y as usize * self.x + pos.z as usize * self.x * self.y)
+        } else {
+            None
+        }
+    }
+
+    /// The position of every chunk, in the order they are stored in the save file.
+    pub fn iter_chunks(&self) -> impl Iterator<Item = Vector3<isize>> {
+        let dimensions = *self;
+        (0..dimensions.z).flat_map(move |z| {
+            (0..dimensions.y).flat_map(move |y| {
+                (0..dimensions.x).map(move |x| Vector3::new(x as isize, y as isize, z as isize))
+            })
+        })
+    }
+}
+
+impl Default for WorldDimensions {
+    fn default() -> Self {
+        WorldDimensions::V0_1_X
+    }
+}
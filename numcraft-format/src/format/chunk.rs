@@ -1,16 +1,13 @@
-use crate::world_converter::numcraft_v0_1_3::constants::BlockType;
-
 use fastnoise_lite::FastNoiseLite;
 use libm::roundf;
 use nalgebra::Vector3;
 
-const CHUNK_SIZE: usize = 8;
+use crate::format::{FormatBlock, BLOCK_COUNT, CHUNK_SIZE};
 
-const BLOCK_COUNT: usize = CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE;
 const CHUNK_SIZE_I: isize = CHUNK_SIZE as isize;
 
-pub struct Chunk {
-    blocks: [BlockType; BLOCK_COUNT],
+pub struct Chunk<B: FormatBlock> {
+    blocks: [B; BLOCK_COUNT],
     pos: Vector3<isize>,
     pub generated: bool,
     pub need_new_mesh: bool,
@@ -18,10 +15,10 @@ pub struct Chunk {
 }
 
 #[allow(dead_code)]
-impl Chunk {
+impl<B: FormatBlock> Chunk<B> {
     pub fn new(pos: Vector3<isize>) -> Self {
         Chunk {
-            blocks: [BlockType::Air; BLOCK_COUNT],
+            blocks: [B::AIR; BLOCK_COUNT],
             pos,
             generated: false,
             need_new_mesh: true,
@@ -29,7 +26,7 @@ impl Chunk {
         }
     }
 
-    pub fn set_at(&mut self, pos: Vector3<usize>, block_type: BlockType) -> bool {
+    pub fn set_at(&mut self, pos: Vector3<usize>, block_type: B) -> bool {
         if pos.x < CHUNK_SIZE && pos.y < CHUNK_SIZE && pos.z < CHUNK_SIZE {
             self.blocks[pos.x + pos.y * CHUNK_SIZE + pos.z * CHUNK_SIZE * CHUNK_SIZE] = block_type;
             true
@@ -38,7 +35,7 @@ impl Chunk {
         }
     }
 
-    pub fn get_at(&self, pos: Vector3<isize>) -> Option<BlockType> {
+    pub fn get_at(&self, pos: Vector3<isize>) -> Option<B> {
         if pos.x < CHUNK_SIZE_I
             && pos.y < CHUNK_SIZE_I
             && pos.z < CHUNK_SIZE_I
@@ -55,7 +52,7 @@ impl Chunk {
         }
     }
 
-    pub fn get_at_unchecked(&self, pos: Vector3<isize>) -> BlockType {
+    pub fn get_at_unchecked(&self, pos: Vector3<isize>) -> B {
         self.blocks[(pos.x + pos.y * CHUNK_SIZE_I + pos.z * CHUNK_SIZE_I * CHUNK_SIZE_I) as usize]
     }
 
@@ -79,10 +76,7 @@ impl Chunk {
 
                 for y in 0..CHUNK_SIZE_I {
                     if chunk_block_pos.y + y >= height {
-                        self.set_at(
-                            Vector3::new(x as usize, y as usize, z as usize),
-                            crate::world_converter::numcraft_v0_1_3::constants::BlockType::Grass,
-                        );
+                        self.set_at(Vector3::new(x as usize, y as usize, z as usize), B::GRASS);
                     }
                 }
             }
@@ -90,7 +84,7 @@ impl Chunk {
         self.generated = true
     }
 
-    pub fn get_all_blocks(&self) -> &[BlockType; BLOCK_COUNT] {
+    pub fn get_all_blocks(&self) -> &[B; BLOCK_COUNT] {
         &self.blocks
     }
 }
@@ -0,0 +1,221 @@
+/* An LZ4 block encoder that looks for the smallest encoding instead of the fastest one. The streams follow the LZ4
+block format, so the decoder of the game reads them like the ones of `lz4_flex::compress`. */
+
+use alloc::{vec, vec::Vec};
+
+const MIN_MATCH: usize = 4;
+/// The last bytes of a block are always literals
+const LAST_LITERALS: usize = 5;
+/// The last match must start this far from the end of the block
+const MATCH_FIND_LIMIT: usize = 12;
+const MAX_OFFSET: usize = u16::MAX as usize;
+/// How many earlier positions are compared when looking for a match
+const MAX_CHAIN_LENGTH: usize = 1024;
+/// Above this length, only the full match is tried and no match is searched inside of it
+const MAX_TRIED_LENGTH: usize = 1024;
+
+/// The hash table grows with the input, up to this size
+const MAX_HASH_BITS: u32 = 16;
+const NO_POSITION: u32 = u32::MAX;
+
+/// The bytes added after the token for a length that doesn't fit in its 4 bits.
+fn get_length_extra_size(length: usize) -> u32 {
+    if length < 15 {
+        0
+    } else {
+        1 + (length - 15) as u32 / 255
+    }
+}
+
+struct MatchFinder {
+    hash_bits: u32,
+    heads: Vec<u32>,
+    previous: Vec<u32>,
+}
+
+impl MatchFinder {
+    fn new(input_len: usize) -> Self {
+        let hash_bits = (usize::BITS - input_len.leading_zeros()).clamp(8, MAX_HASH_BITS);
+        MatchFinder {
+            hash_bits,
+            heads: vec![NO_POSITION; 1 << hash_bits],
+            previous: vec![NO_POSITION; input_len],
+        }
+    }
+
+    fn get_hash(&self, input: &[u8], pos: usize) -> usize {
+        let value =
+            u32::from_le_bytes([input[pos], input[pos + 1], input[pos + 2], input[pos + 3]]);
+        (value.wrapping_mul(2654435761) >> (32 - self.hash_bits)) as usize
+    }
+
+    fn insert(&mut self, input: &[u8], pos: usize) {
+        let hash = self.get_hash(input, pos);
+        self.previous[pos] = self.heads[hash];
+        self.heads[hash] = pos as u32;
+    }
+
+    /// The longest match at `pos` as (length, offset). Every offset costs 2 bytes, so the longest one is enough.
+    fn get_longest_match(
+        &self,
+        input: &[u8],
+        pos: usize,
+        max_length: usize,
+    ) -> Option<(usize, usize)> {
+        let mut best: Option<(usize, usize)> = None;
+        let mut candidate = self.heads[self.get_hash(input, pos)];
+
+        for _ in 0..MAX_CHAIN_LENGTH {
+            if candidate == NO_POSITION || pos - candidate as usize > MAX_OFFSET {
+                break;
+            }
+            let start = candidate as usize;
+            candidate = self.previous[start];
+
+            // A longer match must at least match the byte after the best one
+            if let Some((best_length, _)) = best {
+                if input[start + best_length] != input[pos + best_length] {
+                    continue;
+                }
+            }
+
+            let length = input[start..]
+                .iter()
+                .zip(&input[pos..pos + max_length])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if length >= MIN_MATCH && best.is_none_or(|(best_length, _)| length > best_length) {
+                best = Some((length, pos - start));
+                if length == max_length {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+}
+
+fn write_length_extra(output: &mut Vec<u8>, length: usize) {
+    if length < 15 {
+        return;
+    }
+    let mut remaining = length - 15;
+    while remaining >= 255 {
+        output.push(255);
+        remaining -= 255;
+    }
+    output.push(remaining as u8);
+}
+
+/// Compress `input` to an LZ4 block with an optimal parse: each position keeps the cheapest way to reach it, ending
+/// with a match or with literals, and the cheapest path to the end is written.
+pub fn compress_optimal(input: &[u8]) -> Vec<u8> {
+    let len = input.len();
+
+    // Cost of the input up to a position, when a match ends there. `match_from` is (match start, offset)
+    let mut match_cost = vec![u32::MAX; len + 1];
+    let mut match_from = vec![(0usize, 0usize); len + 1];
+    // Cost of the input up to a position, when literals end there. `literals_start` is where they start
+    let mut literals_cost = vec![0u32; len + 1];
+    let mut literals_start = vec![0usize; len + 1];
+    match_cost[0] = 0;
+
+    let mut match_finder = MatchFinder::new(len);
+    let mut search_from = 0;
+
+    for pos in 0..=len {
+        // The literals go on from the previous position or start after a match ending here
+        if pos == 0 {
+            literals_cost[0] = 0;
+            literals_start[0] = 0;
+        } else {
+            let start = literals_start[pos - 1];
+            let literals_length = pos - start;
+            let extended = literals_cost[pos - 1] + 1 + get_length_extra_size(literals_length)
+                - get_length_extra_size(literals_length - 1);
+
+            if match_cost[pos] <= extended {
+                literals_cost[pos] = match_cost[pos];
+                literals_start[pos] = pos;
+            } else {
+                literals_cost[pos] = extended;
+                literals_start[pos] = start;
+            }
+        }
+
+        if len < MATCH_FIND_LIMIT || pos > len - MATCH_FIND_LIMIT {
+            continue;
+        }
+
+        if pos < search_from {
+            match_finder.insert(input, pos);
+            continue;
+        }
+
+        let longest_match = match_finder.get_longest_match(input, pos, len - LAST_LITERALS - pos);
+        match_finder.insert(input, pos);
+
+        let Some((max_length, offset)) = longest_match else {
+            continue;
+        };
+
+        // A token and an offset, after the literals
+        let start_cost = literals_cost[pos] + 3;
+        let tried_lengths = if max_length > MAX_TRIED_LENGTH {
+            search_from = pos + max_length;
+            max_length..=max_length
+        } else {
+            MIN_MATCH..=max_length
+        };
+        for length in tried_lengths {
+            let cost = start_cost + get_length_extra_size(length - MIN_MATCH);
+            if cost < match_cost[pos + length] {
+                match_cost[pos + length] = cost;
+                match_from[pos + length] = (pos, offset);
+            }
+        }
+    }
+
+    // Walk back from the end: (literals start, match start, match offset, match end)
+    let mut sequences = Vec::new();
+    let mut sequence_end = literals_start[len];
+    while sequence_end > 0 {
+        let (match_start, offset) = match_from[sequence_end];
+        sequences.push((
+            literals_start[match_start],
+            match_start,
+            offset,
+            sequence_end,
+        ));
+        sequence_end = literals_start[match_start];
+    }
+
+    let mut output = Vec::with_capacity(literals_cost[len] as usize + 1);
+    for &(start, match_start, offset, match_end) in sequences.iter().rev() {
+        let literals_length = match_start - start;
+        let match_length = match_end - match_start - MIN_MATCH;
+
+        output.push(((literals_length.min(15) as u8) << 4) | match_length.min(15) as u8);
+        write_length_extra(&mut output, literals_length);
+        output.extend_from_slice(&input[start..match_start]);
+        output.extend_from_slice(&(offset as u16).to_le_bytes());
+        write_length_extra(&mut output, match_length);
+    }
+
+    // The last sequence only has literals
+    let start = literals_start[len];
+    let literals_length = len - start;
+    output.push((literals_length.min(15) as u8) << 4);
+    write_length_extra(&mut output, literals_length);
+    output.extend_from_slice(&input[start..]);
+
+    output
+}
+
+/// Like `lz4_flex::compress_prepend_size`, with the encoding of `compress_optimal`.
+pub fn compress_optimal_prepend_size(input: &[u8]) -> Vec<u8> {
+    let mut output = (input.len() as u32).to_le_bytes().to_vec();
+    output.extend(compress_optimal(input));
+    output
+}
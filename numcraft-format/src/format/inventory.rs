@@ -1,26 +1,27 @@
-use core::{mem, usize};
+use alloc::vec::Vec;
+use core::mem;
 
 use serde::{Deserialize, Serialize};
 
-use crate::world_converter::numcraft_v0_1_3::constants::ItemType;
+use crate::format::FormatItem;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ItemStack {
-    item_type: ItemType,
+pub struct ItemStack<I> {
+    item_type: I,
     amount: u8,
-    pub(crate) creative_slot: bool,
+    pub creative_slot: bool,
 }
 
-impl ItemStack {
+impl<I: FormatItem> ItemStack<I> {
     pub const fn void() -> Self {
         ItemStack {
-            item_type: ItemType::Air,
+            item_type: I::AIR,
             amount: 0,
             creative_slot: false,
         }
     }
 
-    pub fn new(item_type: ItemType, amount: u8, creative_slot: bool) -> Self {
+    pub fn new(item_type: I, amount: u8, creative_slot: bool) -> Self {
         ItemStack {
             item_type,
             amount,
@@ -28,7 +29,7 @@ impl ItemStack {
         }
     }
 
-    pub fn get_item_type(&self) -> ItemType {
+    pub fn get_item_type(&self) -> I {
         self.item_type
     }
     pub fn get_amount(&self) -> u8 {
@@ -41,30 +42,39 @@ impl ItemStack {
 
     pub fn clear(&mut self) {
         self.amount = 0;
-        self.item_type = ItemType::Air;
+        self.item_type = I::AIR;
+    }
+
+    /// The same stack with an item of another version. The saved amount is kept, even for creative slots.
+    pub fn with_item_type<J: FormatItem>(&self, item_type: J) -> ItemStack<J> {
+        ItemStack {
+            item_type,
+            amount: self.amount,
+            creative_slot: self.creative_slot,
+        }
     }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
-pub struct Inventory {
-    slots: Vec<ItemStack>,
+pub struct Inventory<I> {
+    slots: Vec<ItemStack<I>>,
     pub modified: bool,
 }
 
 /// A generic inventory. Can be the player inventory, a chest inventory, etc...
-impl Inventory {
+impl<I: FormatItem> Inventory<I> {
     pub fn new(size: usize) -> Self {
         let mut slots = Vec::with_capacity(size);
         for _ in 0..size {
             slots.push(ItemStack::void());
         }
         Inventory {
-            slots: slots,
+            slots,
             modified: true,
         }
     }
 
-    pub fn fill(&mut self, item_stack: ItemStack) {
+    pub fn fill(&mut self, item_stack: ItemStack<I>) {
         for i in 0..self.slots.len() {
             self.slots[i] = item_stack;
         }
@@ -72,13 +82,13 @@ impl Inventory {
 
     pub fn move_item_in_other_inventory(
         &mut self,
-        other_inventory: &mut Inventory,
+        other_inventory: &mut Inventory<I>,
         start_slot: usize,
         end_slot: usize,
         selected_amount_or_none: Option<usize>,
     ) {
-        let start_slot_itemstack = self.get_ref_to_slot(start_slot).unwrap().clone();
-        let end_slot_itemstack = other_inventory.get_ref_to_slot(end_slot).unwrap().clone();
+        let start_slot_itemstack = *self.get_ref_to_slot(start_slot).unwrap();
+        let end_slot_itemstack = *other_inventory.get_ref_to_slot(end_slot).unwrap();
 
         let start_max_stack_amount =
             start_slot_itemstack.get_item_type().get_max_stack_amount() as usize;
@@ -186,8 +196,8 @@ impl Inventory {
                     );
                 }
             } else {
-                if start_slot_itemstack.item_type != ItemType::Air
-                    && end_slot_itemstack.item_type == ItemType::Air
+                if start_slot_itemstack.item_type != I::AIR
+                    && end_slot_itemstack.item_type == I::AIR
                     && selected_amount != start_slot_itemstack.get_amount() as usize
                 {
                     other_inventory.replace_slot_item_stack(
@@ -214,7 +224,7 @@ impl Inventory {
         }
     }
 
-    pub fn take_one(&mut self, index: usize) -> Option<ItemType> {
+    pub fn take_one(&mut self, index: usize) -> Option<I> {
         let slot = self.slots.get_mut(index)?;
         let item_type = slot.item_type;
         if !slot.creative_slot && slot.amount == 1 {
@@ -235,8 +245,8 @@ impl Inventory {
             return;
         }
 
-        let start_slot_itemstack = self.get_ref_to_slot(start_slot).unwrap().clone();
-        let end_slot_itemstack = self.get_ref_to_slot(end_slot).unwrap().clone();
+        let start_slot_itemstack = *self.get_ref_to_slot(start_slot).unwrap();
+        let end_slot_itemstack = *self.get_ref_to_slot(end_slot).unwrap();
 
         let start_max_stack_amount =
             start_slot_itemstack.get_item_type().get_max_stack_amount() as usize;
@@ -344,8 +354,8 @@ impl Inventory {
                     );
                 }
             } else {
-                if start_slot_itemstack.item_type != ItemType::Air
-                    && end_slot_itemstack.item_type == ItemType::Air
+                if start_slot_itemstack.item_type != I::AIR
+                    && end_slot_itemstack.item_type == I::AIR
                     && selected_amount != start_slot_itemstack.get_amount() as usize
                 {
                     self.replace_slot_item_stack(
@@ -371,7 +381,7 @@ impl Inventory {
         }
     }
 
-    pub fn swap_item_stack(&mut self, slot_index: usize, other: &mut ItemStack) -> Option<()> {
+    pub fn swap_item_stack(&mut self, slot_index: usize, other: &mut ItemStack<I>) -> Option<()> {
         if slot_index >= self.slots.len() {
             None
         } else {
@@ -384,7 +394,7 @@ impl Inventory {
         }
     }
 
-    pub fn get_ref_to_slot_mut(&mut self, slot_index: usize) -> Option<&mut ItemStack> {
+    pub fn get_ref_to_slot_mut(&mut self, slot_index: usize) -> Option<&mut ItemStack<I>> {
         if slot_index >= self.slots.len() {
             None
         } else {
@@ -394,7 +404,7 @@ impl Inventory {
         }
     }
 
-    pub fn get_ref_to_slot(&self, slot_index: usize) -> Option<&ItemStack> {
+    pub fn get_ref_to_slot(&self, slot_index: usize) -> Option<&ItemStack<I>> {
         if slot_index >= self.slots.len() {
             None
         } else {
@@ -418,7 +428,7 @@ impl Inventory {
     pub fn replace_slot_item_stack(
         &mut self,
         slot_index: usize,
-        item_stack: ItemStack,
+        item_stack: ItemStack<I>,
     ) -> Option<()> {
         if slot_index >= self.slots.len() {
             None
@@ -430,12 +440,12 @@ impl Inventory {
         }
     }
 
-    pub fn get_all_slots(&self) -> &Vec<ItemStack> {
+    pub fn get_all_slots(&self) -> &Vec<ItemStack<I>> {
         &self.slots
     }
 
     /// Add an item stack to the inventory. Returns the number of remaining items.
-    pub fn add_item_stack(&mut self, item_stack: ItemStack) -> u8 {
+    pub fn add_item_stack(&mut self, item_stack: ItemStack<I>) -> u8 {
         let max_stack = item_stack.get_item_type().get_max_stack_amount();
         let mut amount = item_stack.amount as usize;
 
@@ -465,7 +475,7 @@ impl Inventory {
 
         // Check for empty slots
         for i in 0..self.slots.len() {
-            if self.slots[i].get_item_type() == ItemType::Air {
+            if self.slots[i].get_item_type() == I::AIR {
                 if amount <= max_stack as usize {
                     self.replace_slot_item_stack(
                         i,
@@ -482,6 +492,6 @@ impl Inventory {
             }
         }
 
-        return amount as u8;
+        amount as u8
     }
 }
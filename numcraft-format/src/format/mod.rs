@@ -0,0 +1,124 @@
+/* The save format shared by every v0.1.x version. A version only declares its blocks, items, header and player data */
+
+use serde::{de::DeserializeOwned, Serialize};
+
+pub mod chunk;
+pub mod compression;
+pub mod inventory;
+pub mod save_manager;
+
+/// Number of blocks along each side of a chunk
+pub const CHUNK_SIZE: usize = 8;
+
+pub const BLOCK_COUNT: usize = CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE;
+
+/// A block of a version, saved as its id in the chunks.
+pub trait FormatBlock: Copy + PartialEq {
+    const AIR: Self;
+    /// The block put by the terrain generator
+    const GRASS: Self;
+
+    fn get_from_id(id: u8) -> Option<Self>;
+
+    fn get_id(&self) -> u8;
+}
+
+/// An item of a version, saved in the inventory of the player.
+pub trait FormatItem: Copy + PartialEq {
+    const AIR: Self;
+
+    fn get_max_stack_amount(&self) -> u8;
+}
+
+/// Everything that changes between two versions of the save format.
+pub trait FormatVersion {
+    type Block: FormatBlock;
+    /// The header of the save file, read without decompressing the world
+    type WorldInfo: Serialize + DeserializeOwned + Clone;
+    type PlayerData: Serialize + DeserializeOwned + Clone;
+
+    fn new_world_info() -> Self::WorldInfo;
+
+    fn new_player_data() -> Self::PlayerData;
+}
+
+/// Declare the blocks or the items of a version with the id saved in the world. The ids must follow the order of
+/// the variants, as postcard saves the index of the variant. An optional `impl FormatBlock` or `impl FormatItem`
+/// block names the variants the format needs.
+macro_rules! saved_ids {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($variant:ident = $id:literal),* $(,)?
+        }
+        $($format_impl:tt)*
+    ) => {
+        $(#[$meta])*
+        #[repr(u8)]
+        pub enum $name {
+            $($variant = $id),*
+        }
+
+        impl $name {
+            pub const fn get_from_id(id: u8) -> Option<Self> {
+                match id {
+                    $($id => Some($name::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+
+        $crate::format::saved_ids!(@impl $name $($format_impl)*);
+    };
+    (@impl $name:ident) => {};
+    (@impl $name:ident impl FormatBlock { AIR = $air:ident, GRASS = $grass:ident $(,)? }) => {
+        impl $crate::format::FormatBlock for $name {
+            const AIR: Self = $name::$air;
+            const GRASS: Self = $name::$grass;
+
+            fn get_from_id(id: u8) -> Option<Self> {
+                $name::get_from_id(id)
+            }
+
+            fn get_id(&self) -> u8 {
+                *self as u8
+            }
+        }
+    };
+    (@impl $name:ident impl FormatItem { AIR = $air:ident $(,)? }) => {
+        impl $crate::format::FormatItem for $name {
+            const AIR: Self = $name::$air;
+
+            fn get_max_stack_amount(&self) -> u8 {
+                $name::get_max_stack_amount(self)
+            }
+        }
+    };
+}
+
+/// Load the registry of a version once. The JSON is given with `include_str!` so the path stays relative to the
+/// version module.
+macro_rules! version_registry {
+    ($json:expr, $version:literal) => {
+        pub mod registry {
+            use spin::Lazy;
+
+            use crate::registry::Registry;
+
+            static REGISTRY: Lazy<Registry> = Lazy::new(|| {
+                Registry::from_json($json).expect(concat!(
+                    "The ",
+                    $version,
+                    " registry is invalid."
+                ))
+            });
+
+            pub fn get_registry() -> &'static Registry {
+                &REGISTRY
+            }
+        }
+    };
+}
+
+pub(crate) use saved_ids;
+pub(crate) use version_registry;
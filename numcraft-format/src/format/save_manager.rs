@@ -0,0 +1,363 @@
+use alloc::{vec, vec::Vec};
+
+use lz4_flex::{compress, compress_prepend_size, decompress, decompress_size_prepended};
+use nalgebra::Vector3;
+use postcard::{from_bytes, to_allocvec};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    dimensions::WorldDimensions,
+    format::{
+        chunk::Chunk,
+        compression::{compress_optimal, compress_optimal_prepend_size},
+        FormatBlock, FormatVersion, BLOCK_COUNT, CHUNK_SIZE,
+    },
+    limits::{check_save_file, LimitError},
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum GameMode {
+    Survival,
+    Creative,
+}
+
+/// The file a save manager was loaded from, split like `get_raw` writes it
+#[derive(Clone)]
+struct LoadedFile {
+    header: Vec<u8>,
+    content: Vec<u8>,
+    size: usize,
+}
+
+pub struct SaveManager<F: FormatVersion> {
+    dimensions: WorldDimensions,
+    chunks_data: Vec<Vec<u8>>,
+    loaded_file: Option<LoadedFile>,
+    pub player_data: F::PlayerData,
+    pub world_info: F::WorldInfo,
+}
+
+impl<F: FormatVersion> Clone for SaveManager<F> {
+    fn clone(&self) -> Self {
+        SaveManager {
+            dimensions: self.dimensions,
+            chunks_data: self.chunks_data.clone(),
+            loaded_file: self.loaded_file.clone(),
+            player_data: self.player_data.clone(),
+            world_info: self.world_info.clone(),
+        }
+    }
+}
+
+impl<F: FormatVersion> SaveManager<F> {
+    pub fn new() -> Self {
+        SaveManager::with_dimensions(WorldDimensions::V0_1_X)
+    }
+
+    pub fn with_dimensions(dimensions: WorldDimensions) -> Self {
+        SaveManager {
+            dimensions,
+            chunks_data: vec![Vec::new(); dimensions.get_chunk_count()],
+            loaded_file: None,
+            player_data: F::new_player_data(),
+            world_info: F::new_world_info(),
+        }
+    }
+
+    pub fn get_dimensions(&self) -> WorldDimensions {
+        self.dimensions
+    }
+
+    pub fn set_chunk(&mut self, chunk: &Chunk<F::Block>) -> bool {
+        let Some(index) = self.dimensions.get_chunk_index(*chunk.get_pos()) else {
+            return false;
+        };
+
+        let compressed = compress(&chunk.get_all_blocks().map(|b| b.get_id()));
+
+        self.chunks_data[index] = compressed;
+
+        true
+    }
+
+    /// The size of the compressed data of the chunk, as it is saved in the file.
+    pub fn get_chunk_data_size(&self, pos: Vector3<isize>) -> Option<usize> {
+        Some(self.chunks_data[self.dimensions.get_chunk_index(pos)?].len())
+    }
+
+    /// The header and the data compressed after it.
+    fn get_parts(&self) -> (Vec<u8>, Vec<u8>) {
+        let mut header: Vec<u8> = Vec::new();
+
+        let raw_world_info = to_allocvec(&self.world_info).unwrap();
+        header.extend((raw_world_info.len() as u16).to_be_bytes());
+        header.extend(raw_world_info);
+
+        let mut data_to_compress: Vec<u8> = Vec::new();
+
+        for i in 0..self.chunks_data.len() {
+            let size: u16 = self.chunks_data[i].len() as u16;
+            data_to_compress.extend_from_slice(&size.to_be_bytes());
+        }
+
+        for i in 0..self.chunks_data.len() {
+            data_to_compress.extend(&self.chunks_data[i]);
+        }
+
+        let raw_player_data = to_allocvec(&self.player_data).unwrap();
+        data_to_compress.extend((raw_player_data.len() as u16).to_be_bytes());
+        data_to_compress.extend(raw_player_data);
+
+        (header, data_to_compress)
+    }
+
+    pub fn get_raw(&self) -> Vec<u8> {
+        let (mut data, data_to_compress) = self.get_parts();
+        data.extend_from_slice(&compress_prepend_size(&data_to_compress));
+
+        data
+    }
+
+    /// The size of the loaded file while its content didn't change, since it may be compressed differently than by
+    /// `get_raw`. The size of `get_raw` otherwise.
+    pub fn get_file_size(&self) -> usize {
+        let (header, data_to_compress) = self.get_parts();
+        match &self.loaded_file {
+            Some(loaded) if loaded.header == header && loaded.content == data_to_compress => {
+                loaded.size
+            }
+            _ => header.len() + compress_prepend_size(&data_to_compress).len(),
+        }
+    }
+
+    fn read_world_info(&mut self, data: &[u8]) -> Result<usize, SaveFileLoadError> {
+        let mut world_data_offset = 0;
+        // If world info is missing, the world is currupted
+        if world_data_offset + 1 >= data.len() {
+            return Err(SaveFileLoadError::CorruptedWorld);
+        }
+
+        // Extract world info
+        let world_info_size =
+            u16::from_be_bytes([data[world_data_offset], data[world_data_offset + 1]]) as usize;
+
+        world_data_offset += 2; // world info size
+
+        // Check for overflow
+        if world_data_offset + world_info_size > data.len() {
+            return Err(SaveFileLoadError::CorruptedWorld);
+        }
+
+        // Read the raw data
+        let world_info_raw = &data[world_data_offset..(world_data_offset + world_info_size)];
+
+        if let Ok(world_info) = from_bytes::<F::WorldInfo>(world_info_raw) {
+            self.world_info = world_info;
+            Ok(world_data_offset + world_info_size)
+        } else {
+            Err(SaveFileLoadError::CorruptedWorld)
+        }
+    }
+
+    pub fn load_from_file(&mut self, raw_data: Vec<u8>) -> Result<(), SaveFileLoadError> {
+        check_save_file(&raw_data, self.dimensions).map_err(SaveFileLoadError::LimitExceeded)?;
+
+        if let Ok(world_data_offset) = self.read_world_info(&raw_data) {
+            // Decompress the entire file
+            if let Ok(data) = decompress_size_prepended(&raw_data[world_data_offset..]) {
+                let chunk_count = self.dimensions.get_chunk_count();
+
+                // Check that the chunk sizes header is complete
+                if chunk_count * 2 > data.len() {
+                    return Err(SaveFileLoadError::CorruptedWorld);
+                }
+
+                let mut current_pos = chunk_count * 2;
+                for i in 0..chunk_count {
+                    let size = u16::from_be_bytes([data[i * 2], data[i * 2 + 1]]) as usize; // Get the compressed chunk size from the headers
+
+                    if current_pos + size > data.len() {
+                        // Check for corruption. If overflow, the size is wrong and the world is ... unusable ...
+                        return Err(SaveFileLoadError::CorruptedWorld);
+                    }
+                    let raw_chunk = &data[current_pos..(current_pos + size)];
+
+                    self.chunks_data[i] = raw_chunk.to_vec();
+
+                    current_pos += size;
+                }
+
+                // If player data is missing, the world is currupted
+                if current_pos + 1 >= data.len() {
+                    return Err(SaveFileLoadError::CorruptedWorld);
+                }
+
+                // Extract player_data
+                let player_data_size =
+                    u16::from_be_bytes([data[current_pos], data[current_pos + 1]]) as usize;
+
+                current_pos += 2; // player data size
+
+                // Check for overflow
+                if current_pos + player_data_size > data.len() {
+                    return Err(SaveFileLoadError::CorruptedWorld);
+                }
+
+                // Read the raw data
+                let player_data_raw = &data[current_pos..(current_pos + player_data_size)];
+
+                if let Ok(player_data) = from_bytes::<F::PlayerData>(player_data_raw) {
+                    self.player_data = player_data;
+                } else {
+                    return Err(SaveFileLoadError::CorruptedWorld);
+                }
+
+                self.loaded_file = Some(LoadedFile {
+                    header: raw_data[..world_data_offset].to_vec(),
+                    size: raw_data.len(),
+                    content: data,
+                });
+
+                Ok(())
+            } else {
+                Err(SaveFileLoadError::CorruptedWorld)
+            }
+        } else {
+            Err(SaveFileLoadError::CorruptedWorld)
+        }
+    }
+
+    pub fn get_chunk_at_pos(
+        &self,
+        pos: Vector3<isize>,
+    ) -> Result<Chunk<F::Block>, ChunkReadingError> {
+        let Some(index) = self.dimensions.get_chunk_index(pos) else {
+            return Err(ChunkReadingError::OOBChunk);
+        };
+
+        let raw_chunk = &self.chunks_data[index];
+
+        if let Ok(chunk_data) = decompress(raw_chunk, BLOCK_COUNT) {
+            if chunk_data.len() != BLOCK_COUNT {
+                return Err(ChunkReadingError::CorruptedChunk);
+            }
+
+            let mut chunk = Chunk::new(pos);
+
+            for x in 0..CHUNK_SIZE {
+                for y in 0..CHUNK_SIZE {
+                    for z in 0..CHUNK_SIZE {
+                        if let Some(block_type) = F::Block::get_from_id(
+                            chunk_data[x + y * CHUNK_SIZE + z * CHUNK_SIZE * CHUNK_SIZE],
+                        ) {
+                            chunk.set_at(Vector3::new(x, y, z), block_type);
+                        } else {
+                            return Err(ChunkReadingError::CorruptedChunk);
+                        }
+                    }
+                }
+            }
+
+            Ok(chunk)
+        } else {
+            Err(ChunkReadingError::CorruptedChunk)
+        }
+    }
+
+    pub fn clean(&mut self) {
+        for chunk in self.chunks_data.iter_mut() {
+            chunk.clear();
+        }
+
+        self.player_data = F::new_player_data();
+    }
+}
+
+impl<F: FormatVersion> Default for SaveManager<F> {
+    fn default() -> Self {
+        SaveManager::new()
+    }
+}
+
+/// Re-encode a save file with `compress_optimal`. The header, the blocks and the player are kept as they are, only the
+/// compression changes, so this works for every version with the chunks of `dimensions`. The result is never larger
+/// than `raw`.
+pub fn optimize_world(raw: &[u8], dimensions: WorldDimensions) -> Option<Vec<u8>> {
+    check_save_file(raw, dimensions).ok()?;
+
+    let world_info_size = u16::from_be_bytes([*raw.first()?, *raw.get(1)?]) as usize;
+    let header = raw.get(..2 + world_info_size)?;
+    let data = decompress_size_prepended(&raw[header.len()..]).ok()?;
+
+    let chunk_count = dimensions.get_chunk_count();
+    let mut chunks = Vec::with_capacity(chunk_count);
+    let mut current_pos = chunk_count * 2;
+    for i in 0..chunk_count {
+        let size = u16::from_be_bytes([*data.get(i * 2)?, *data.get(i * 2 + 1)?]) as usize;
+        let raw_chunk = data.get(current_pos..current_pos + size)?;
+        current_pos += size;
+
+        // A chunk that was never saved stays empty
+        if raw_chunk.is_empty() {
+            chunks.push(Vec::new());
+            continue;
+        }
+
+        let blocks = decompress(raw_chunk, BLOCK_COUNT).ok()?;
+        if blocks.len() != BLOCK_COUNT {
+            return None;
+        }
+        let optimized = compress_optimal(&blocks);
+        if optimized.len() < raw_chunk.len() {
+            chunks.push(optimized);
+        } else {
+            chunks.push(raw_chunk.to_vec());
+        }
+    }
+
+    let mut data_to_compress: Vec<u8> = Vec::with_capacity(data.len());
+    for chunk in &chunks {
+        data_to_compress.extend_from_slice(&(chunk.len() as u16).to_be_bytes());
+    }
+    for chunk in &chunks {
+        data_to_compress.extend(chunk);
+    }
+    // The player data and its size
+    data_to_compress.extend(&data[current_pos..]);
+
+    let mut optimized = header.to_vec();
+    optimized.extend(compress_optimal_prepend_size(&data_to_compress));
+
+    if optimized.len() < raw.len() {
+        Some(optimized)
+    } else {
+        Some(raw.to_vec())
+    }
+}
+
+#[derive(Debug)]
+pub enum ChunkReadingError {
+    OOBChunk,
+    CorruptedChunk,
+}
+
+#[derive(Debug)]
+pub enum SaveFileLoadError {
+    FileNotFound,
+    CorruptedWorld,
+    /// The file is larger than any world could be
+    LimitExceeded(LimitError),
+}
+
+/*
+Save file format. World is 4 x 4 x 4 chunks for every v0.1.x version (see WorldDimensions).
+
+Header:
+    X*Y*Z x 2 B array : represent the compressed size of the chunk for each chunk
+
+    X*Y*Z x variable size : chunks data.
+
+    2 + variable : Player info
+
+    2 + variable : World Info
+*/
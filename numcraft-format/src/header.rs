@@ -0,0 +1,143 @@
+/* Read the header of a save file of any version, without decompressing the world */
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    format::save_manager::GameMode,
+    limits::{check_header, MAX_WORLD_NAME_SIZE},
+};
+
+/// The version of a save file, as far as its header tells.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveVersion {
+    V0_0_7_,
+    V0_1_0,
+    /// The header has a `world_version`. 0 is v0.1.3, anything else is a fork or a newer version of the game
+    Numbered(u16),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WorldHeader {
+    pub save_version: SaveVersion,
+    pub world_name: String,
+    pub gamemode: GameMode,
+}
+
+// v0.1.3
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WorldInfo3 {
+    pub world_version: u16,
+    pub world_name: String,
+    pub world_seed: i32,
+    pub gamemode: GameMode,
+}
+
+// v0.1.0
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WorldInfo2 {
+    pub world_name: String,
+    pub world_seed: i32,
+    pub gamemode: GameMode,
+}
+
+// v0.0.7 - v0.0.9
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WorldInfo1 {
+    pub world_name: String,
+    pub world_seed: i32,
+}
+
+/// Read the header of a save file. Files outside of the limits of the format are rejected.
+pub fn get_world_info(raw: &[u8]) -> Option<WorldHeader> {
+    check_header(raw).ok()?;
+    read_world_info(raw)
+}
+
+pub(crate) fn read_world_info(raw: &[u8]) -> Option<WorldHeader> {
+    if raw.len() < 2 {
+        return None;
+    }
+
+    let world_info_size = u16::from_be_bytes([raw[0], raw[1]]) as usize;
+
+    if raw.len() < 2 + world_info_size {
+        return None;
+    }
+
+    // Try different versions
+    if let Ok(world_info) = postcard::from_bytes::<WorldInfo3>(raw.get(2..(2 + world_info_size))?) {
+        Some(WorldHeader {
+            save_version: SaveVersion::Numbered(world_info.world_version),
+            world_name: world_info.world_name,
+            gamemode: world_info.gamemode,
+        })
+    } else if let Ok(world_info) =
+        postcard::from_bytes::<WorldInfo2>(raw.get(2..(2 + world_info_size))?)
+    {
+        Some(WorldHeader {
+            save_version: SaveVersion::V0_1_0,
+            world_name: world_info.world_name,
+            gamemode: world_info.gamemode,
+        })
+    } else if let Ok(world_info) =
+        postcard::from_bytes::<WorldInfo1>(raw.get(2..(2 + world_info_size))?)
+    {
+        Some(WorldHeader {
+            save_version: SaveVersion::V0_0_7_,
+            world_name: world_info.world_name,
+            gamemode: GameMode::Creative,
+        })
+    } else {
+        None
+    }
+}
+
+/// Rename a world by only rewriting its header. The content is kept byte for byte, so this works with any version.
+pub fn rename_world(raw: &[u8], name: &str) -> Option<Vec<u8>> {
+    check_header(raw).ok()?;
+    if name.len() > MAX_WORLD_NAME_SIZE {
+        return None;
+    }
+
+    let world_info_size = u16::from_be_bytes([*raw.first()?, *raw.get(1)?]) as usize;
+    let raw_world_info = raw.get(2..2 + world_info_size)?;
+
+    // Fields added after the ones known here are kept as they are
+    let mut new_world_info = if let Ok((mut world_info, remaining)) =
+        postcard::take_from_bytes::<WorldInfo3>(raw_world_info)
+    {
+        world_info.world_name = name.to_string();
+        let mut data = postcard::to_allocvec(&world_info).ok()?;
+        data.extend(remaining);
+        data
+    } else if let Ok((mut world_info, remaining)) =
+        postcard::take_from_bytes::<WorldInfo2>(raw_world_info)
+    {
+        world_info.world_name = name.to_string();
+        let mut data = postcard::to_allocvec(&world_info).ok()?;
+        data.extend(remaining);
+        data
+    } else if let Ok((mut world_info, remaining)) =
+        postcard::take_from_bytes::<WorldInfo1>(raw_world_info)
+    {
+        world_info.world_name = name.to_string();
+        let mut data = postcard::to_allocvec(&world_info).ok()?;
+        data.extend(remaining);
+        data
+    } else {
+        return None;
+    };
+
+    let mut data = (u16::try_from(new_world_info.len()).ok()?)
+        .to_be_bytes()
+        .to_vec();
+    data.append(&mut new_world_info);
+    data.extend(&raw[2 + world_info_size..]);
+
+    Some(data)
+}
@@ -0,0 +1,613 @@
+/* Break a save file into annotated regions, to see where a world that doesn't load is broken. The reading goes on
+after a problem whenever the layout allows it. */
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use lz4_flex::decompress;
+
+use crate::{
+    dimensions::WorldDimensions,
+    format::BLOCK_COUNT,
+    header::{read_world_info, SaveVersion},
+    limits::{
+        MAX_COMPRESSED_CHUNK_SIZE, MAX_DECOMPRESSED_SIZE, MAX_FILE_SIZE, MAX_WORLD_NAME_SIZE,
+    },
+    numcraft_v0_1_0, numcraft_v0_1_3,
+    registry::Registry,
+};
+
+/// The bytes a region points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteSpace {
+    File,
+    /// The chunks and the player data, once decompressed
+    Content,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region {
+    pub space: ByteSpace,
+    pub offset: usize,
+    pub size: usize,
+    pub label: String,
+    /// The decoded value
+    pub value: String,
+    /// Why this region keeps the world from loading
+    pub problem: Option<String>,
+}
+
+pub struct Inspection {
+    pub file: Vec<u8>,
+    /// None if the content couldn't be decompressed
+    pub content: Option<Vec<u8>>,
+    pub version: Option<SaveVersion>,
+    pub regions: Vec<Region>,
+}
+
+impl Inspection {
+    pub fn get_bytes(&self, region: &Region) -> &[u8] {
+        let bytes = match region.space {
+            ByteSpace::File => &self.file,
+            ByteSpace::Content => self.content.as_deref().unwrap_or_default(),
+        };
+        bytes
+            .get(region.offset..region.offset + region.size)
+            .unwrap_or_default()
+    }
+
+    pub fn get_problems(&self) -> impl Iterator<Item = &Region> {
+        self.regions
+            .iter()
+            .filter(|region| region.problem.is_some())
+    }
+}
+
+/// Reads postcard fields one by one and records a region for each of them.
+struct FieldReader<'a> {
+    data: &'a [u8],
+    space: ByteSpace,
+    /// Where `data` starts in its byte space
+    base: usize,
+    pos: usize,
+    regions: Vec<Region>,
+}
+
+impl<'a> FieldReader<'a> {
+    fn new(data: &'a [u8], space: ByteSpace, base: usize) -> Self {
+        FieldReader {
+            data,
+            space,
+            base,
+            pos: 0,
+            regions: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, label: &str, size: usize, value: String, problem: Option<String>) {
+        self.regions.push(Region {
+            space: self.space,
+            offset: self.base + self.pos,
+            size,
+            label: label.to_string(),
+            value,
+            problem,
+        });
+        self.pos += size;
+    }
+
+    /// Mark the rest of the data as unreadable.
+    fn fail(&mut self, label: &str) {
+        let size = self.data.len() - self.pos;
+        self.push(
+            label,
+            size,
+            format!("{size} bytes"),
+            Some(format!(
+                "The data ends or is invalid here, {label} can't be read."
+            )),
+        );
+    }
+
+    fn get_varint(&self, max_size: usize) -> Option<(u64, usize)> {
+        let mut value = 0u64;
+        for i in 0..max_size {
+            let byte = *self.data.get(self.pos + i)?;
+            value |= ((byte & 0x7F) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Some((value, i + 1));
+            }
+        }
+        None
+    }
+
+    fn read_varint(&mut self, label: &str, max_size: usize) -> Option<u64> {
+        let Some((value, size)) = self.get_varint(max_size) else {
+            self.fail(label);
+            return None;
+        };
+        self.push(label, size, value.to_string(), None);
+        Some(value)
+    }
+
+    fn read_zigzag(&mut self, label: &str) -> Option<i64> {
+        let Some((value, size)) = self.get_varint(5) else {
+            self.fail(label);
+            return None;
+        };
+        let value = (value >> 1) as i64 ^ -((value & 1) as i64);
+        self.push(label, size, value.to_string(), None);
+        Some(value)
+    }
+
+    fn read_string(&mut self, label: &str, max_length: usize) -> Option<String> {
+        let Some((length, length_size)) = self.get_varint(5) else {
+            self.fail(label);
+            return None;
+        };
+        // The length comes from the file, so it may not fit in memory on 32 bits
+        let start = self.pos + length_size;
+        let Some(bytes) = usize::try_from(length)
+            .ok()
+            .and_then(|length| start.checked_add(length))
+            .and_then(|end| self.data.get(start..end))
+        else {
+            self.fail(label);
+            return None;
+        };
+
+        let Ok(text) = core::str::from_utf8(bytes) else {
+            self.push(
+                label,
+                length_size + bytes.len(),
+                format!("{length} bytes"),
+                Some("The text isn't valid UTF-8.".to_string()),
+            );
+            return None;
+        };
+        let problem = (bytes.len() > max_length)
+            .then(|| format!("The text is longer than {max_length} bytes."));
+        self.push(
+            label,
+            length_size + bytes.len(),
+            format!("\"{text}\""),
+            problem,
+        );
+        Some(text.to_string())
+    }
+
+    fn read_floats(&mut self, label: &str, count: usize) -> Option<Vec<f32>> {
+        let Some(bytes) = self.data.get(self.pos..self.pos + count * 4) else {
+            self.fail(label);
+            return None;
+        };
+        let floats: Vec<f32> = bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+
+        let value = floats
+            .iter()
+            .map(|f| format!("{f}"))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let problem = floats
+            .iter()
+            .any(|f| !f.is_finite())
+            .then(|| "The value isn't a finite number.".to_string());
+        self.push(label, count * 4, format!("({value})"), problem);
+        Some(floats)
+    }
+
+    fn read_game_mode(&mut self) -> Option<()> {
+        let Some((value, size)) = self.get_varint(5) else {
+            self.fail("Game mode");
+            return None;
+        };
+        let (value, problem) = match value {
+            0 => ("Survival".to_string(), None),
+            1 => ("Creative".to_string(), None),
+            _ => (value.to_string(), Some("Unknown game mode.".to_string())),
+        };
+        self.push("Game mode", size, value, problem);
+        Some(())
+    }
+
+    fn read_bool(&mut self, label: &str) -> Option<bool> {
+        let Some(&byte) = self.data.get(self.pos) else {
+            self.fail(label);
+            return None;
+        };
+        let problem = (byte > 1).then(|| "A boolean must be 0 or 1.".to_string());
+        self.push(label, 1, (byte == 1).to_string(), problem);
+        Some(byte == 1)
+    }
+
+    /// An item stack: the item id, the amount and the creative slot flag.
+    fn read_slot(&mut self, slot: usize, registry: Option<&Registry>) -> Option<()> {
+        let label = format!("Slot {slot}");
+        let Some((item_id, id_size)) = self.get_varint(5) else {
+            self.fail(&label);
+            return None;
+        };
+        let (Some(&amount), Some(&creative_slot)) = (
+            self.data.get(self.pos + id_size),
+            self.data.get(self.pos + id_size + 1),
+        ) else {
+            self.fail(&label);
+            return None;
+        };
+
+        let item_name = registry.map(|r| u8::try_from(item_id).ok().and_then(|id| r.get_item(id)));
+        let mut value = match item_name {
+            Some(Some(item)) => format!("{} x {amount}", item.name),
+            _ => format!("item {item_id} x {amount}"),
+        };
+        if creative_slot == 1 {
+            value += ", creative";
+        }
+        let problem = if matches!(item_name, Some(None)) {
+            Some(format!(
+                "The item id {item_id} doesn't exist in this version."
+            ))
+        } else if creative_slot > 1 {
+            Some("The creative slot flag must be 0 or 1.".to_string())
+        } else {
+            None
+        };
+        self.push(&label, id_size + 2, value, problem);
+        Some(())
+    }
+
+    /// Any byte left is not part of the known layout.
+    fn finish(mut self, problem: Option<&str>) -> Vec<Region> {
+        if self.pos < self.data.len() {
+            let size = self.data.len() - self.pos;
+            self.push(
+                "Unknown bytes",
+                size,
+                format!("{size} bytes"),
+                problem.map(|p| p.to_string()),
+            );
+        }
+        self.regions
+    }
+}
+
+fn read_header_fields(reader: &mut FieldReader, version: SaveVersion) -> Option<()> {
+    if let SaveVersion::Numbered(_) = version {
+        reader.read_varint("World version", 3)?;
+    }
+    reader.read_string("World name", MAX_WORLD_NAME_SIZE)?;
+    reader.read_zigzag("World seed")?;
+    if version != SaveVersion::V0_0_7_ {
+        reader.read_game_mode()?;
+    }
+    Some(())
+}
+
+fn read_player_fields(reader: &mut FieldReader, registry: Option<&Registry>) -> Option<()> {
+    reader.read_floats("Player position", 3)?;
+    reader.read_floats("Player rotation", 2)?;
+    let slot_count = reader.read_varint("Inventory size", 5)?;
+    for slot in 0..slot_count as usize {
+        reader.read_slot(slot, registry)?;
+    }
+    reader.read_bool("Inventory modified")?;
+    Some(())
+}
+
+fn get_version_registry(version: Option<SaveVersion>) -> Option<&'static Registry> {
+    match version? {
+        SaveVersion::V0_1_0 => Some(numcraft_v0_1_0::registry::get_registry()),
+        SaveVersion::Numbered(0) => Some(numcraft_v0_1_3::registry::get_registry()),
+        _ => None,
+    }
+}
+
+fn format_ratio(size: usize, total: usize) -> String {
+    format!("{:.1}%", size as f32 / total.max(1) as f32 * 100.)
+}
+
+fn inspect_content(
+    content: &[u8],
+    dimensions: WorldDimensions,
+    registry: Option<&Registry>,
+) -> Vec<Region> {
+    let mut regions = Vec::new();
+    let mut region =
+        |offset: usize, size: usize, label: String, value: String, problem: Option<String>| {
+            regions.push(Region {
+                space: ByteSpace::Content,
+                offset,
+                size,
+                label,
+                value,
+                problem,
+            })
+        };
+
+    let chunk_count = dimensions.get_chunk_count();
+    let Some(table) = content.get(..chunk_count * 2) else {
+        region(
+            0,
+            content.len(),
+            "Chunk sizes".to_string(),
+            format!("{} bytes", content.len()),
+            Some(format!(
+                "The table of the {chunk_count} chunk sizes is incomplete."
+            )),
+        );
+        return regions;
+    };
+    let sizes: Vec<usize> = table
+        .chunks_exact(2)
+        .map(|s| u16::from_be_bytes([s[0], s[1]]) as usize)
+        .collect();
+    let total: usize = sizes.iter().sum();
+    region(
+        0,
+        table.len(),
+        "Chunk sizes".to_string(),
+        format!("{chunk_count} chunks, {total} bytes"),
+        None,
+    );
+
+    let mut current_pos = table.len();
+    for (pos, size) in dimensions.iter_chunks().zip(sizes) {
+        let label = format!("Chunk ({}, {}, {})", pos.x, pos.y, pos.z);
+        let Some(raw_chunk) = content.get(current_pos..current_pos + size) else {
+            region(
+                current_pos,
+                content.len().saturating_sub(current_pos),
+                label,
+                format!("{size} bytes"),
+                Some("The chunk goes past the end of the data, its size is wrong.".to_string()),
+            );
+            return regions;
+        };
+
+        let (value, problem) = if size == 0 {
+            ("Never generated".to_string(), None)
+        } else if size > MAX_COMPRESSED_CHUNK_SIZE {
+            (
+                format!("{size} bytes"),
+                Some(format!(
+                    "A chunk can't be larger than {MAX_COMPRESSED_CHUNK_SIZE} bytes."
+                )),
+            )
+        } else {
+            match decompress(raw_chunk, BLOCK_COUNT) {
+                Ok(blocks) if blocks.len() == BLOCK_COUNT => {
+                    let unknown = registry.map_or(0, |r| {
+                        blocks
+                            .iter()
+                            .filter(|id| r.get_block(**id).is_none())
+                            .count()
+                    });
+                    (
+                        format!(
+                            "{size} bytes for {BLOCK_COUNT} blocks, {}",
+                            format_ratio(size, BLOCK_COUNT)
+                        ),
+                        (unknown > 0).then(|| {
+                            format!(
+                                "{unknown} blocks have an id that doesn't exist in this version."
+                            )
+                        }),
+                    )
+                }
+                Ok(blocks) => (
+                    format!("{size} bytes"),
+                    Some(format!(
+                        "The chunk has {} blocks instead of {BLOCK_COUNT}.",
+                        blocks.len()
+                    )),
+                ),
+                Err(error) => (
+                    format!("{size} bytes"),
+                    Some(format!("The chunk can't be decompressed: {error:?}.")),
+                ),
+            }
+        };
+        region(current_pos, size, label, value, problem);
+        current_pos += size;
+    }
+
+    let Some(player_data_size) = content
+        .get(current_pos..current_pos + 2)
+        .map(|s| u16::from_be_bytes([s[0], s[1]]) as usize)
+    else {
+        region(
+            current_pos,
+            content.len() - current_pos,
+            "Player data size".to_string(),
+            String::new(),
+            Some("The player data is missing.".to_string()),
+        );
+        return regions;
+    };
+    region(
+        current_pos,
+        2,
+        "Player data size".to_string(),
+        format!("{player_data_size} bytes"),
+        None,
+    );
+    current_pos += 2;
+
+    let player_data_end = (current_pos + player_data_size).min(content.len());
+    let mut reader = FieldReader::new(
+        &content[current_pos..player_data_end],
+        ByteSpace::Content,
+        current_pos,
+    );
+    read_player_fields(&mut reader, registry);
+    regions.extend(reader.finish(Some(
+        "These bytes of the player data aren't read by the game.",
+    )));
+
+    if player_data_end < current_pos + player_data_size {
+        regions.push(Region {
+            space: ByteSpace::Content,
+            offset: player_data_end,
+            size: 0,
+            label: "End of the data".to_string(),
+            value: String::new(),
+            problem: Some(format!(
+                "The player data should be {player_data_size} bytes long, the data ends after {}.",
+                player_data_end - current_pos
+            )),
+        });
+    } else if player_data_end < content.len() {
+        regions.push(Region {
+            space: ByteSpace::Content,
+            offset: player_data_end,
+            size: content.len() - player_data_end,
+            label: "Unknown bytes".to_string(),
+            value: format!("{} bytes", content.len() - player_data_end),
+            problem: None,
+        });
+    }
+
+    regions
+}
+
+/// Break a save file into regions. Worlds of every version are read with the v0.1.x layout, except the content of
+/// the v0.0.x worlds.
+pub fn inspect_world(raw: &[u8]) -> Inspection {
+    let version = read_world_info(raw).map(|header| header.save_version);
+    let mut inspection = Inspection {
+        file: raw.to_vec(),
+        content: None,
+        version,
+        regions: Vec::new(),
+    };
+
+    let mut reader = FieldReader::new(raw, ByteSpace::File, 0);
+    let Some(world_info_size) = raw
+        .get(..2)
+        .map(|s| u16::from_be_bytes([s[0], s[1]]) as usize)
+    else {
+        reader.fail("World info size");
+        inspection.regions = reader.finish(None);
+        return inspection;
+    };
+    reader.push(
+        "World info size",
+        2,
+        format!("{world_info_size} bytes"),
+        None,
+    );
+
+    let header_end = (2 + world_info_size).min(raw.len());
+    let mut header_reader = FieldReader::new(&raw[2..header_end], ByteSpace::File, 2);
+    match version {
+        Some(version) => {
+            read_header_fields(&mut header_reader, version);
+        }
+        None => header_reader.fail("World info"),
+    }
+    inspection.regions.extend(reader.regions);
+    inspection.regions.extend(header_reader.finish(None));
+
+    if header_end < 2 + world_info_size {
+        inspection.regions.push(Region {
+            space: ByteSpace::File,
+            offset: header_end,
+            size: 0,
+            label: "End of the file".to_string(),
+            value: String::new(),
+            problem: Some("The file ends inside of the header.".to_string()),
+        });
+        return inspection;
+    }
+
+    let body = &raw[header_end..];
+    if version == Some(SaveVersion::V0_0_7_) {
+        inspection.regions.push(Region {
+            space: ByteSpace::File,
+            offset: header_end,
+            size: body.len(),
+            label: "Content".to_string(),
+            value: format!(
+                "{} bytes, the layout of the v0.0.x worlds isn't inspected",
+                body.len()
+            ),
+            problem: None,
+        });
+        return inspection;
+    }
+
+    let Some(content_size) = body
+        .get(..4)
+        .map(|s| u32::from_le_bytes([s[0], s[1], s[2], s[3]]) as usize)
+    else {
+        inspection.regions.push(Region {
+            space: ByteSpace::File,
+            offset: header_end,
+            size: body.len(),
+            label: "Decompressed size".to_string(),
+            value: String::new(),
+            problem: Some("The file ends before the size of the content.".to_string()),
+        });
+        return inspection;
+    };
+    inspection.regions.push(Region {
+        space: ByteSpace::File,
+        offset: header_end,
+        size: 4,
+        label: "Decompressed size".to_string(),
+        value: format!("{content_size} bytes"),
+        problem: (content_size > MAX_DECOMPRESSED_SIZE).then(|| {
+            format!("The content of a world can't be larger than {MAX_DECOMPRESSED_SIZE} bytes.")
+        }),
+    });
+
+    let compressed = &body[4..];
+    let mut compressed_region = Region {
+        space: ByteSpace::File,
+        offset: header_end + 4,
+        size: compressed.len(),
+        label: "Compressed content".to_string(),
+        value: format!(
+            "{} bytes, {} of the decompressed size",
+            compressed.len(),
+            format_ratio(compressed.len(), content_size)
+        ),
+        problem: (raw.len() > MAX_FILE_SIZE)
+            .then(|| format!("A world can't be larger than {MAX_FILE_SIZE} bytes.")),
+    };
+    if content_size > MAX_DECOMPRESSED_SIZE {
+        inspection.regions.push(compressed_region);
+        return inspection;
+    }
+
+    match decompress(compressed, content_size) {
+        Ok(content) => {
+            if content.len() != content_size {
+                compressed_region.problem = Some(format!(
+                    "The content is {} bytes long instead of {content_size}.",
+                    content.len()
+                ));
+            }
+            inspection.regions.push(compressed_region);
+            inspection.regions.extend(inspect_content(
+                &content,
+                WorldDimensions::V0_1_X,
+                get_version_registry(version),
+            ));
+            inspection.content = Some(content);
+        }
+        Err(error) => {
+            compressed_region.problem =
+                Some(format!("The content can't be decompressed: {error:?}."));
+            inspection.regions.push(compressed_region);
+        }
+    }
+
+    inspection
+}
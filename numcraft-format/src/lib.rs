@@ -0,0 +1,14 @@
+/* The save format of the Numcraft worlds. It runs on the calculator too, so it only needs `alloc` */
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod dimensions;
+pub mod format;
+pub mod header;
+pub mod inspect;
+pub mod limits;
+pub mod numcraft_v0_1_0;
+pub mod numcraft_v0_1_3;
+pub mod registry;
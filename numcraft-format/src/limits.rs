@@ -0,0 +1,106 @@
+/* Hard limits of a save file, derived from the format. A file outside of them is rejected before anything is
+decompressed, so a forged size can't make the reader allocate more than the largest real world needs. */
+
+use alloc::{format, string::String};
+
+use crate::{dimensions::WorldDimensions, format::BLOCK_COUNT, header::read_world_info};
+
+/// Every version saves 4 x 4 x 4 chunks
+pub const MAX_CHUNK_COUNT: usize = WorldDimensions::V0_1_X.get_chunk_count();
+
+/// The header is saved after its size, as a u16
+pub const MAX_WORLD_INFO_SIZE: usize = u16::MAX as usize;
+
+/// The largest name that fits in the header of every version. The name is saved after its length, a varint of 3 bytes
+/// at this size, and the v0.1.3 header also holds the world version (up to 3 bytes), the seed (up to 5 bytes) and the
+/// gamemode (1 byte).
+pub const MAX_WORLD_NAME_SIZE: usize = MAX_WORLD_INFO_SIZE - 3 - 3 - 5 - 1;
+
+/// A chunk that LZ4 can't compress at all
+pub const MAX_COMPRESSED_CHUNK_SIZE: usize = get_compress_bound(BLOCK_COUNT);
+
+/// The player data is saved after its size, as a u16
+pub const MAX_PLAYER_DATA_SIZE: usize = u16::MAX as usize;
+
+/// The chunk sizes, the chunks and the player data, before they are compressed together
+pub const MAX_DECOMPRESSED_SIZE: usize =
+    MAX_CHUNK_COUNT * (2 + MAX_COMPRESSED_CHUNK_SIZE) + 2 + MAX_PLAYER_DATA_SIZE;
+
+/// The header, then the size of the decompressed data and the data
+pub const MAX_FILE_SIZE: usize =
+    2 + MAX_WORLD_INFO_SIZE + 4 + get_compress_bound(MAX_DECOMPRESSED_SIZE);
+
+/// The largest LZ4 block for `size` bytes of input.
+const fn get_compress_bound(size: usize) -> usize {
+    size + size / 255 + 16
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitError {
+    FileTooLarge { size: usize },
+    WorldNameTooLong { size: usize },
+    TooManyChunks { count: usize },
+    DecompressedTooLarge { size: usize },
+}
+
+impl LimitError {
+    pub fn get_description(&self) -> String {
+        match self {
+            LimitError::FileTooLarge { size } => format!(
+                "The file is {size} bytes long, a world can't be larger than {MAX_FILE_SIZE} bytes."
+            ),
+            LimitError::WorldNameTooLong { size } => format!(
+                "The world name is {size} bytes long, the maximum is {MAX_WORLD_NAME_SIZE} bytes."
+            ),
+            LimitError::TooManyChunks { count } => format!(
+                "The world has {count} chunks, the maximum is {MAX_CHUNK_COUNT}."
+            ),
+            LimitError::DecompressedTooLarge { size } => format!(
+                "The world says it decompresses to {size} bytes, a world can't be larger than {MAX_DECOMPRESSED_SIZE} bytes."
+            ),
+        }
+    }
+}
+
+/// Check the parts of a save file that every version shares: its size and its header.
+pub fn check_header(raw: &[u8]) -> Result<(), LimitError> {
+    if raw.len() > MAX_FILE_SIZE {
+        return Err(LimitError::FileTooLarge { size: raw.len() });
+    }
+
+    if let Some(header) = read_world_info(raw) {
+        if header.world_name.len() > MAX_WORLD_NAME_SIZE {
+            return Err(LimitError::WorldNameTooLong {
+                size: header.world_name.len(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a save file with the v0.1.x layout before its content is decompressed.
+pub fn check_save_file(raw: &[u8], dimensions: WorldDimensions) -> Result<(), LimitError> {
+    check_header(raw)?;
+
+    let chunk_count = dimensions.get_chunk_count();
+    if chunk_count > MAX_CHUNK_COUNT {
+        return Err(LimitError::TooManyChunks { count: chunk_count });
+    }
+
+    // The size of the decompressed data is written before it
+    let Some(world_info_size) = raw
+        .get(..2)
+        .map(|s| u16::from_be_bytes([s[0], s[1]]) as usize)
+    else {
+        return Ok(());
+    };
+    if let Some(size) = raw.get(2 + world_info_size..2 + world_info_size + 4) {
+        let size = u32::from_le_bytes([size[0], size[1], size[2], size[3]]) as usize;
+        if size > MAX_DECOMPRESSED_SIZE {
+            return Err(LimitError::DecompressedTooLarge { size });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{format::saved_ids, numcraft_v0_1_0::registry::get_registry};
+
+saved_ids! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub enum BlockType {
+        Air = 0,
+        Stone = 1,
+        Grass = 2,
+        Dirt = 3,
+    }
+
+    impl FormatBlock {
+        AIR = Air,
+        GRASS = Grass,
+    }
+}
+
+saved_ids! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+    pub enum ItemType {
+        Air = 0,
+
+        StoneBlock = 1,
+        GrassBlock = 2,
+        DirtBlock = 3,
+    }
+
+    impl FormatItem {
+        AIR = Air,
+    }
+}
+
+impl ItemType {
+    pub fn get_max_stack_amount(&self) -> u8 {
+        get_registry()
+            .get_item(*self as u8)
+            .map_or(0, |item| item.max_stack_amount)
+    }
+}
@@ -0,0 +1,71 @@
+use alloc::string::String;
+
+use crate::format::{version_registry, FormatVersion};
+
+pub mod constants;
+
+version_registry!(include_str!("registry.json"), "v0.1.0");
+
+pub struct V0_1_0;
+
+impl FormatVersion for V0_1_0 {
+    type Block = constants::BlockType;
+    type WorldInfo = save_manager::WorldInfo;
+    type PlayerData = save_manager::PlayerData;
+
+    fn new_world_info() -> Self::WorldInfo {
+        save_manager::WorldInfo {
+            world_name: String::new(),
+            world_seed: 1,
+            gamemode: save_manager::GameMode::Survival,
+        }
+    }
+
+    fn new_player_data() -> Self::PlayerData {
+        save_manager::PlayerData {
+            pos: (0., 0., 0.),
+            rotation: (0., 0.),
+            inventory: inventory::Inventory::new(0),
+        }
+    }
+}
+
+pub mod inventory {
+    use crate::{format, numcraft_v0_1_0::constants::ItemType};
+
+    pub type ItemStack = format::inventory::ItemStack<ItemType>;
+    pub type Inventory = format::inventory::Inventory<ItemType>;
+}
+
+pub mod save_manager {
+    use alloc::string::String;
+    use serde::{Deserialize, Serialize};
+
+    pub use crate::format::save_manager::GameMode;
+    use crate::{
+        format,
+        numcraft_v0_1_0::{inventory::Inventory, V0_1_0},
+    };
+
+    pub type SaveManager = format::save_manager::SaveManager<V0_1_0>;
+
+    #[derive(Serialize, Deserialize, Clone)]
+    pub struct PlayerData {
+        pub pos: (f32, f32, f32),
+        pub rotation: (f32, f32), // Only Pitch and Yaw
+        pub inventory: Inventory,
+    }
+
+    #[derive(Serialize, Deserialize, Clone)]
+    pub struct WorldInfo {
+        pub world_name: String,
+        pub world_seed: i32,
+        pub gamemode: GameMode,
+    }
+
+    impl SaveManager {
+        pub fn get_game_mode(&self) -> GameMode {
+            self.world_info.gamemode
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    format::saved_ids,
+    numcraft_v0_1_3::registry::get_registry,
+    registry::{BlockDefinition, ItemDefinition},
+};
+
+pub mod save_manager {
+    /// Changed by the game at each update that breaks the worlds
+    pub const WORLD_VERSION: u16 = 0;
+
+    pub const PLAYER_INVENTORY_SIZE: usize = 24;
+}
+
+pub mod world {
+    pub use crate::format::CHUNK_SIZE;
+}
+
+saved_ids! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+    pub enum BlockType {
+        Air = 0,
+        Stone = 1,
+        Grass = 2,
+        Dirt = 3,
+        Sand = 4,
+        Cobblestone = 5,
+        Border = 6,
+        Log = 7,
+        Leaves = 8,
+        Planks = 9,
+    }
+
+    impl FormatBlock {
+        AIR = Air,
+        GRASS = Grass,
+    }
+}
+
+saved_ids! {
+    #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+    pub enum ItemType {
+        Air = 0,
+
+        StoneBlock = 1,
+        GrassBlock = 2,
+        DirtBlock = 3,
+        SandBlock = 4,
+        CobblestoneBlock = 5,
+        BorderBlock = 6,
+        LogBlock = 7,
+        LeavesBlock = 8,
+        PlanksBlock = 9,
+    }
+
+    impl FormatItem {
+        AIR = Air,
+    }
+}
+
+impl ItemType {
+    pub fn get_definition(&self) -> &'static ItemDefinition {
+        get_registry()
+            .get_item(*self as u8)
+            .expect("The item is missing from the registry.")
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        &self.get_definition().name
+    }
+
+    pub fn get_max_stack_amount(&self) -> u8 {
+        self.get_definition().max_stack_amount
+    }
+
+    pub fn get_matching_block_type(&self) -> Option<BlockType> {
+        BlockType::get_from_id(get_registry().get_item_block(*self as u8)?.id)
+    }
+}
+
+impl BlockType {
+    pub fn is_air(&self) -> bool {
+        *self == BlockType::Air
+    }
+
+    pub fn get_definition(&self) -> &'static BlockDefinition {
+        get_registry()
+            .get_block(*self as u8)
+            .expect("The block is missing from the registry.")
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        &self.get_definition().name
+    }
+
+    pub fn get_hardness(&self) -> f32 {
+        self.get_definition().hardness
+    }
+
+    pub fn get_dropped_item_type(&self) -> ItemType {
+        get_registry()
+            .get_block_drop(*self as u8)
+            .and_then(|item| ItemType::get_from_id(item.id))
+            .unwrap_or(ItemType::Air)
+    }
+}
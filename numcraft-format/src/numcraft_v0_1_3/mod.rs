@@ -0,0 +1,102 @@
+use alloc::string::String;
+
+use crate::format::{version_registry, FormatVersion};
+
+pub mod constants;
+
+version_registry!(include_str!("registry.json"), "v0.1.3");
+
+pub struct V0_1_3;
+
+impl FormatVersion for V0_1_3 {
+    type Block = constants::BlockType;
+    type WorldInfo = save_manager::WorldInfo;
+    type PlayerData = save_manager::PlayerData;
+
+    fn new_world_info() -> Self::WorldInfo {
+        save_manager::WorldInfo {
+            world_version: constants::save_manager::WORLD_VERSION,
+            world_name: String::new(),
+            world_seed: 1,
+            gamemode: save_manager::GameMode::Survival,
+        }
+    }
+
+    fn new_player_data() -> Self::PlayerData {
+        save_manager::PlayerData {
+            pos: (0., 0., 0.),
+            rotation: (0., 0.),
+            inventory: inventory::Inventory::new(constants::save_manager::PLAYER_INVENTORY_SIZE),
+        }
+    }
+}
+
+pub mod chunk {
+    pub type Chunk = crate::format::chunk::Chunk<super::constants::BlockType>;
+}
+
+pub mod inventory {
+    use crate::{format, numcraft_v0_1_3::constants::ItemType};
+
+    pub type ItemStack = format::inventory::ItemStack<ItemType>;
+    pub type Inventory = format::inventory::Inventory<ItemType>;
+}
+
+pub mod save_manager {
+    use alloc::string::{String, ToString};
+    use nalgebra::Vector3;
+    use serde::{Deserialize, Serialize};
+
+    pub use crate::format::save_manager::{ChunkReadingError, GameMode, SaveFileLoadError};
+    use crate::{
+        format,
+        numcraft_v0_1_3::{inventory::Inventory, V0_1_3},
+    };
+
+    pub type SaveManager = format::save_manager::SaveManager<V0_1_3>;
+
+    #[derive(Serialize, Deserialize, Clone)]
+    pub struct PlayerData {
+        pub pos: (f32, f32, f32),
+        pub rotation: (f32, f32), // Only Pitch and Yaw
+        pub inventory: Inventory,
+    }
+
+    #[derive(Serialize, Deserialize, Clone)]
+    pub struct WorldInfo {
+        pub world_version: u16,
+        pub world_name: String,
+        pub world_seed: i32,
+        pub gamemode: GameMode,
+    }
+
+    impl SaveManager {
+        pub fn set_world_seed(&mut self, seed: i32) {
+            self.world_info.world_seed = seed;
+        }
+
+        pub fn set_world_name(&mut self, world_name: &str) {
+            self.world_info.world_name = world_name.to_string();
+        }
+
+        pub fn set_gamemode(&mut self, gamemode: GameMode) {
+            self.world_info.gamemode = gamemode;
+        }
+
+        pub fn get_game_mode(&self) -> GameMode {
+            self.world_info.gamemode
+        }
+
+        pub fn get_player_pos(&self) -> Vector3<f32> {
+            Vector3::new(
+                self.player_data.pos.0,
+                self.player_data.pos.1,
+                self.player_data.pos.2,
+            )
+        }
+
+        pub fn get_player_rot(&self) -> Vector3<f32> {
+            Vector3::new(self.player_data.rotation.0, self.player_data.rotation.1, 0.)
+        }
+    }
+}
@@ -0,0 +1,190 @@
+/* The blocks and items of a game version, described as data so every version shares the same code */
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Category {
+    Air,
+    #[default]
+    Natural,
+    Plant,
+    Building,
+    Technical,
+}
+
+// Defaults for the registries of forks, which often only give the ids and names
+fn get_default_hardness() -> f32 {
+    1.
+}
+
+fn get_default_color() -> String {
+    "rgb(255, 0, 255)".to_string()
+}
+
+fn get_default_max_stack_amount() -> u8 {
+    64
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockDefinition {
+    pub id: u8,
+    pub name: String,
+    /// The block can't be mined if the hardness is negative
+    #[serde(default = "get_default_hardness")]
+    pub hardness: f32,
+    /// Name of the item dropped when the block is mined
+    #[serde(default)]
+    pub drop: Option<String>,
+    /// CSS color used to draw the block on the map
+    #[serde(default = "get_default_color")]
+    pub color: String,
+    #[serde(default)]
+    pub category: Category,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemDefinition {
+    pub id: u8,
+    pub name: String,
+    #[serde(default = "get_default_max_stack_amount")]
+    pub max_stack_amount: u8,
+    /// Name of the block placed by the item
+    #[serde(default)]
+    pub block: Option<String>,
+    #[serde(default)]
+    pub category: Category,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Registry {
+    pub blocks: Vec<BlockDefinition>,
+    pub items: Vec<ItemDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegistryError {
+    Syntax(String),
+    DuplicateBlockId(u8),
+    DuplicateItemId(u8),
+    DuplicateName(String),
+    UnknownBlock(String),
+    UnknownItem(String),
+}
+
+impl RegistryError {
+    pub fn get_description(&self) -> String {
+        match self {
+            RegistryError::Syntax(error) => format!("The registry can't be read: {error}"),
+            RegistryError::DuplicateBlockId(id) => format!("Two blocks have the id {id}."),
+            RegistryError::DuplicateItemId(id) => format!("Two items have the id {id}."),
+            RegistryError::DuplicateName(name) => format!("The name \"{name}\" is used twice."),
+            RegistryError::UnknownBlock(name) => format!("There is no block named \"{name}\"."),
+            RegistryError::UnknownItem(name) => format!("There is no item named \"{name}\"."),
+        }
+    }
+}
+
+/// Converts the ids of a registry to the ids of another one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdMapping {
+    ids: Vec<Option<u8>>,
+}
+
+impl IdMapping {
+    /// Returns None if the target registry has nothing with the same name.
+    pub fn get(&self, id: u8) -> Option<u8> {
+        self.ids.get(id as usize).copied().flatten()
+    }
+}
+
+impl Registry {
+    pub fn from_json(text: &str) -> Result<Self, RegistryError> {
+        let registry: Registry =
+            serde_json::from_str(text).map_err(|error| RegistryError::Syntax(error.to_string()))?;
+        registry.check()?;
+
+        Ok(registry)
+    }
+
+    /// Make sure every id and name is unique and that the drops and placed blocks exist.
+    pub fn check(&self) -> Result<(), RegistryError> {
+        for (i, block) in self.blocks.iter().enumerate() {
+            if self.blocks[..i].iter().any(|b| b.id == block.id) {
+                return Err(RegistryError::DuplicateBlockId(block.id));
+            }
+            if self.blocks[..i].iter().any(|b| b.name == block.name) {
+                return Err(RegistryError::DuplicateName(block.name.clone()));
+            }
+            if let Some(drop) = &block.drop {
+                self.get_item_by_name(drop)
+                    .ok_or(RegistryError::UnknownItem(drop.clone()))?;
+            }
+        }
+
+        for (i, item) in self.items.iter().enumerate() {
+            if self.items[..i].iter().any(|it| it.id == item.id) {
+                return Err(RegistryError::DuplicateItemId(item.id));
+            }
+            if self.items[..i].iter().any(|it| it.name == item.name) {
+                return Err(RegistryError::DuplicateName(item.name.clone()));
+            }
+            if let Some(block) = &item.block {
+                self.get_block_by_name(block)
+                    .ok_or(RegistryError::UnknownBlock(block.clone()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_block(&self, id: u8) -> Option<&BlockDefinition> {
+        self.blocks.iter().find(|block| block.id == id)
+    }
+
+    pub fn get_block_by_name(&self, name: &str) -> Option<&BlockDefinition> {
+        self.blocks.iter().find(|block| block.name == name)
+    }
+
+    pub fn get_item(&self, id: u8) -> Option<&ItemDefinition> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    pub fn get_item_by_name(&self, name: &str) -> Option<&ItemDefinition> {
+        self.items.iter().find(|item| item.name == name)
+    }
+
+    /// The item dropped when the block is mined.
+    pub fn get_block_drop(&self, id: u8) -> Option<&ItemDefinition> {
+        self.get_item_by_name(self.get_block(id)?.drop.as_ref()?)
+    }
+
+    /// The block placed by the item.
+    pub fn get_item_block(&self, id: u8) -> Option<&BlockDefinition> {
+        self.get_block_by_name(self.get_item(id)?.block.as_ref()?)
+    }
+
+    /// Match the blocks of the two registries by name.
+    pub fn get_block_id_mapping(&self, target: &Registry) -> IdMapping {
+        let mut ids = vec![None; u8::MAX as usize + 1];
+        for block in &self.blocks {
+            ids[block.id as usize] = target.get_block_by_name(&block.name).map(|b| b.id);
+        }
+        IdMapping { ids }
+    }
+
+    /// Match the items of the two registries by name.
+    pub fn get_item_id_mapping(&self, target: &Registry) -> IdMapping {
+        let mut ids = vec![None; u8::MAX as usize + 1];
+        for item in &self.items {
+            ids[item.id as usize] = target.get_item_by_name(&item.name).map(|it| it.id);
+        }
+        IdMapping { ids }
+    }
+}
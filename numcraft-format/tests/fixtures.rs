@@ -0,0 +1,158 @@
+/* Every fixture must parse, report the right header and come out of a load/save round trip unchanged */
+
+use numcraft_format::{
+    format::save_manager::GameMode,
+    header::{get_world_info, rename_world, SaveVersion},
+    numcraft_v0_1_0, numcraft_v0_1_3,
+};
+
+const KINDS: [&str; 5] = ["empty", "built", "survival", "creative", "max_inventory"];
+
+macro_rules! fixture {
+    ($name:literal) => {
+        (
+            $name,
+            include_bytes!(concat!("fixtures/", $name, ".ncw")).as_slice(),
+        )
+    };
+}
+
+const V0_1_0_FIXTURES: [(&str, &[u8]); 5] = [
+    fixture!("v0_1_0_empty"),
+    fixture!("v0_1_0_built"),
+    fixture!("v0_1_0_survival"),
+    fixture!("v0_1_0_creative"),
+    fixture!("v0_1_0_max_inventory"),
+];
+
+const V0_1_3_FIXTURES: [(&str, &[u8]); 5] = [
+    fixture!("v0_1_3_empty"),
+    fixture!("v0_1_3_built"),
+    fixture!("v0_1_3_survival"),
+    fixture!("v0_1_3_creative"),
+    fixture!("v0_1_3_max_inventory"),
+];
+
+const V0_0_7_FIXTURE: &[u8] = include_bytes!("fixtures/v0_0_7_header.ncw");
+
+fn get_expected_game_mode(kind: &str) -> GameMode {
+    if kind == "creative" {
+        GameMode::Creative
+    } else {
+        GameMode::Survival
+    }
+}
+
+#[test]
+fn world_info_of_every_fixture() {
+    let header = get_world_info(V0_0_7_FIXTURE).unwrap();
+    assert_eq!(header.save_version, SaveVersion::V0_0_7_);
+    assert_eq!(header.world_name, "v0.0.7 world");
+    assert_eq!(header.gamemode, GameMode::Creative);
+
+    for ((name, raw), kind) in V0_1_0_FIXTURES.iter().zip(KINDS) {
+        let header = get_world_info(raw).unwrap();
+        assert_eq!(header.save_version, SaveVersion::V0_1_0, "{name}");
+        assert_eq!(header.world_name, format!("v0.1.0 {kind}"));
+        assert_eq!(header.gamemode, get_expected_game_mode(kind), "{name}");
+    }
+
+    for ((name, raw), kind) in V0_1_3_FIXTURES.iter().zip(KINDS) {
+        let header = get_world_info(raw).unwrap();
+        assert_eq!(header.save_version, SaveVersion::Numbered(0), "{name}");
+        assert_eq!(header.world_name, format!("v0.1.3 {kind}"));
+        assert_eq!(header.gamemode, get_expected_game_mode(kind), "{name}");
+    }
+}
+
+#[test]
+fn truncated_headers_are_rejected() {
+    let (_, raw) = V0_1_3_FIXTURES[1];
+    assert!(get_world_info(&raw[..1]).is_none());
+    assert!(get_world_info(&raw[..4]).is_none());
+    assert!(get_world_info(&[]).is_none());
+}
+
+#[test]
+fn v0_1_0_fixtures_parse() {
+    for (name, raw) in V0_1_0_FIXTURES {
+        let mut save_manager = numcraft_v0_1_0::save_manager::SaveManager::new();
+        save_manager.load_from_file(raw.to_vec()).unwrap();
+
+        for pos in save_manager.get_dimensions().iter_chunks() {
+            assert!(save_manager.get_chunk_at_pos(pos).is_ok(), "{name} {pos:?}");
+        }
+        assert_eq!(save_manager.player_data.inventory.get_all_slots().len(), 24);
+    }
+}
+
+#[test]
+fn v0_1_3_fixtures_parse() {
+    for (name, raw) in V0_1_3_FIXTURES {
+        let mut save_manager = numcraft_v0_1_3::save_manager::SaveManager::new();
+        save_manager.load_from_file(raw.to_vec()).unwrap();
+
+        for pos in save_manager.get_dimensions().iter_chunks() {
+            assert!(save_manager.get_chunk_at_pos(pos).is_ok(), "{name} {pos:?}");
+        }
+        assert_eq!(save_manager.world_info.world_seed, 1234, "{name}");
+    }
+}
+
+#[test]
+fn v0_1_3_inventories() {
+    let load = |raw: &[u8]| {
+        let mut save_manager = numcraft_v0_1_3::save_manager::SaveManager::new();
+        save_manager.load_from_file(raw.to_vec()).unwrap();
+        save_manager.player_data.inventory
+    };
+
+    let empty = load(V0_1_3_FIXTURES[0].1);
+    assert!(empty
+        .get_all_slots()
+        .iter()
+        .all(|slot| slot.get_amount() == 0));
+
+    let creative = load(V0_1_3_FIXTURES[3].1);
+    let creative_slots = creative
+        .get_all_slots()
+        .iter()
+        .filter(|slot| slot.creative_slot);
+    assert_eq!(creative_slots.count(), 9);
+
+    let full = load(V0_1_3_FIXTURES[4].1);
+    assert!(full
+        .get_all_slots()
+        .iter()
+        .all(|slot| slot.get_amount() == slot.get_item_type().get_max_stack_amount()));
+}
+
+#[test]
+fn round_trips_are_byte_stable() {
+    for (name, raw) in V0_1_0_FIXTURES {
+        let mut save_manager = numcraft_v0_1_0::save_manager::SaveManager::new();
+        save_manager.load_from_file(raw.to_vec()).unwrap();
+        assert_eq!(save_manager.get_raw(), raw, "{name}");
+    }
+
+    for (name, raw) in V0_1_3_FIXTURES {
+        let mut save_manager = numcraft_v0_1_3::save_manager::SaveManager::new();
+        save_manager.load_from_file(raw.to_vec()).unwrap();
+        assert_eq!(save_manager.get_raw(), raw, "{name}");
+    }
+}
+
+#[test]
+fn rename_keeps_the_content() {
+    for (name, raw) in V0_1_0_FIXTURES.iter().chain(&V0_1_3_FIXTURES) {
+        let renamed = rename_world(raw, "Renamed").unwrap();
+        assert_eq!(get_world_info(&renamed).unwrap().world_name, "Renamed");
+
+        let header_size = |data: &[u8]| u16::from_be_bytes([data[0], data[1]]) as usize + 2;
+        assert_eq!(
+            renamed[header_size(&renamed)..],
+            raw[header_size(raw)..],
+            "{name}"
+        );
+    }
+}
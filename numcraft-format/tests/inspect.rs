@@ -0,0 +1,164 @@
+/* The inspector must describe every byte of a world and point at the region that breaks it */
+
+use lz4_flex::{compress_prepend_size, decompress_size_prepended};
+use numcraft_format::inspect::{inspect_world, ByteSpace, Inspection};
+
+const WORLDS: [&[u8]; 4] = [
+    include_bytes!("fixtures/v0_1_0_survival.ncw"),
+    include_bytes!("fixtures/v0_1_3_built.ncw"),
+    include_bytes!("fixtures/v0_1_3_max_inventory.ncw"),
+    include_bytes!("fixtures/v0_0_7_header.ncw"),
+];
+
+/// The regions of a byte space follow each other and cover all of it.
+fn assert_covered(inspection: &Inspection, space: ByteSpace, size: usize) {
+    let mut end = 0;
+    for region in inspection.regions.iter().filter(|r| r.space == space) {
+        assert_eq!(region.offset, end, "{region:?}");
+        end += region.size;
+    }
+    assert_eq!(end, size);
+}
+
+fn get_region<'a>(inspection: &'a Inspection, label: &str) -> &'a numcraft_format::inspect::Region {
+    inspection
+        .regions
+        .iter()
+        .find(|r| r.label == label)
+        .unwrap_or_else(|| panic!("no region {label}"))
+}
+
+/// The world with its content replaced.
+fn with_content(raw: &[u8], edit: impl FnOnce(&mut Vec<u8>)) -> Vec<u8> {
+    let header_size = 2 + u16::from_be_bytes([raw[0], raw[1]]) as usize;
+    let mut content = decompress_size_prepended(&raw[header_size..]).unwrap();
+    edit(&mut content);
+
+    let mut data = raw[..header_size].to_vec();
+    data.extend(compress_prepend_size(&content));
+    data
+}
+
+#[test]
+fn valid_worlds_have_no_problem() {
+    for raw in WORLDS {
+        let inspection = inspect_world(raw);
+        assert_eq!(
+            inspection.get_problems().count(),
+            0,
+            "{:?}",
+            inspection.regions
+        );
+        assert_covered(&inspection, ByteSpace::File, raw.len());
+        if let Some(content) = &inspection.content {
+            assert_covered(&inspection, ByteSpace::Content, content.len());
+        }
+    }
+}
+
+#[test]
+fn fields_are_decoded() {
+    let inspection = inspect_world(WORLDS[1]);
+    assert_eq!(get_region(&inspection, "World version").value, "0");
+    assert_eq!(
+        get_region(&inspection, "World name").value,
+        "\"v0.1.3 built\""
+    );
+    assert_eq!(get_region(&inspection, "World seed").value, "1234");
+    assert_eq!(get_region(&inspection, "Game mode").value, "Survival");
+    assert_eq!(
+        get_region(&inspection, "Player position").value,
+        "(14.5, 6, 14.5)"
+    );
+    assert_eq!(get_region(&inspection, "Inventory size").value, "24");
+    assert_eq!(
+        get_region(&inspection, "Chunk sizes")
+            .value
+            .split(',')
+            .next(),
+        Some("64 chunks")
+    );
+
+    let chunks = inspection
+        .regions
+        .iter()
+        .filter(|r| r.label.starts_with("Chunk ("))
+        .count();
+    assert_eq!(chunks, 64);
+
+    let world_name = get_region(&inspection, "World name");
+    assert_eq!(&inspection.get_bytes(world_name)[1..], b"v0.1.3 built");
+
+    let full = inspect_world(WORLDS[2]);
+    assert_eq!(get_region(&full, "Slot 0").value, "Stone Block x 64");
+}
+
+#[test]
+fn a_broken_chunk_is_pointed_at() {
+    let raw = with_content(WORLDS[1], |content| {
+        // The first chunk of the second row
+        let size_0 = u16::from_be_bytes([content[0], content[1]]) as usize;
+        let start = 128 + size_0;
+        content[start] = 0xFF;
+        content[start + 1] = 0xFF;
+    });
+
+    let inspection = inspect_world(&raw);
+    let problems: Vec<_> = inspection.get_problems().collect();
+    assert_eq!(problems.len(), 1, "{problems:?}");
+    assert_eq!(problems[0].label, "Chunk (1, 0, 0)");
+    assert_covered(
+        &inspection,
+        ByteSpace::Content,
+        inspection.content.as_ref().unwrap().len(),
+    );
+}
+
+#[test]
+fn a_truncated_player_is_pointed_at() {
+    let raw = with_content(WORLDS[1], |content| {
+        content.truncate(content.len() - 10);
+    });
+
+    let inspection = inspect_world(&raw);
+    assert!(inspection
+        .get_problems()
+        .any(|r| r.label.starts_with("Slot") || r.label == "End of the data"));
+}
+
+#[test]
+fn broken_files_are_still_described() {
+    let raw = WORLDS[1];
+
+    let truncated = inspect_world(&raw[..raw.len() - 20]);
+    assert!(truncated.content.is_none());
+    assert!(get_region(&truncated, "Compressed content")
+        .problem
+        .is_some());
+    assert_covered(&truncated, ByteSpace::File, raw.len() - 20);
+
+    let mut bomb = raw.to_vec();
+    let header_size = 2 + u16::from_be_bytes([raw[0], raw[1]]) as usize;
+    bomb[header_size..header_size + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    let inspection = inspect_world(&bomb);
+    assert!(get_region(&inspection, "Decompressed size")
+        .problem
+        .is_some());
+    assert!(inspection.content.is_none());
+
+    for size in [0, 1, 5, header_size + 2] {
+        let inspection = inspect_world(&raw[..size]);
+        assert!(inspection.get_problems().count() > 0, "{size}");
+        assert_covered(&inspection, ByteSpace::File, size);
+    }
+}
+
+#[test]
+fn the_largest_header_size_is_pointed_at() {
+    let mut raw = 0xFFFF_u16.to_be_bytes().to_vec();
+    raw.extend([0; 8]);
+
+    let inspection = inspect_world(&raw);
+    assert!(inspection.get_problems().count() > 0);
+    assert_covered(&inspection, ByteSpace::File, raw.len());
+}
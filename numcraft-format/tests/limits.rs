@@ -0,0 +1,112 @@
+/* Forged files must be rejected before they are decompressed */
+
+use numcraft_format::{
+    dimensions::WorldDimensions,
+    format::save_manager::{optimize_world, SaveFileLoadError},
+    header::{get_world_info, rename_world},
+    limits::{
+        check_header, check_save_file, LimitError, MAX_DECOMPRESSED_SIZE, MAX_FILE_SIZE,
+        MAX_WORLD_NAME_SIZE,
+    },
+    numcraft_v0_1_3,
+};
+
+const WORLD: &[u8] = include_bytes!("fixtures/v0_1_3_built.ncw");
+
+fn get_world_info_size(raw: &[u8]) -> usize {
+    u16::from_be_bytes([raw[0], raw[1]]) as usize
+}
+
+/// The world with another size written before its compressed data.
+fn with_decompressed_size(size: u32) -> Vec<u8> {
+    let mut raw = WORLD.to_vec();
+    let offset = 2 + get_world_info_size(WORLD);
+    raw[offset..offset + 4].copy_from_slice(&size.to_le_bytes());
+    raw
+}
+
+#[test]
+fn the_fixtures_are_within_the_limits() {
+    for raw in [
+        WORLD,
+        include_bytes!("fixtures/v0_1_0_max_inventory.ncw").as_slice(),
+        include_bytes!("fixtures/v0_0_7_header.ncw").as_slice(),
+    ] {
+        assert_eq!(check_header(raw), Ok(()));
+    }
+    assert_eq!(check_save_file(WORLD, WorldDimensions::V0_1_X), Ok(()));
+}
+
+#[test]
+fn the_largest_header_sizes_are_read_safely() {
+    for world_info_size in [0xFFFE_u16, 0xFFFF] {
+        let mut raw = world_info_size.to_be_bytes().to_vec();
+        raw.extend([0; 8]);
+        assert_eq!(check_header(&raw), Ok(()));
+        assert!(get_world_info(&raw).is_none());
+        assert!(check_save_file(&raw, WorldDimensions::V0_1_X).is_ok());
+
+        // A header of this size, filled with zeros, is an unnamed v0.0.7 world
+        raw.resize(2 + world_info_size as usize, 0);
+        assert_eq!(check_header(&raw), Ok(()));
+        assert!(get_world_info(&raw).is_some());
+    }
+}
+
+#[test]
+fn a_forged_decompressed_size_is_rejected() {
+    let raw = with_decompressed_size(u32::MAX);
+    assert_eq!(
+        check_save_file(&raw, WorldDimensions::V0_1_X),
+        Err(LimitError::DecompressedTooLarge {
+            size: u32::MAX as usize
+        })
+    );
+
+    let mut save_manager = numcraft_v0_1_3::save_manager::SaveManager::new();
+    assert!(matches!(
+        save_manager.load_from_file(raw.clone()),
+        Err(SaveFileLoadError::LimitExceeded(
+            LimitError::DecompressedTooLarge { .. }
+        ))
+    ));
+    assert!(optimize_world(&raw, WorldDimensions::V0_1_X).is_none());
+
+    // The header is still readable, the world can be listed and deleted
+    assert!(get_world_info(&raw).is_some());
+
+    let raw = with_decompressed_size(MAX_DECOMPRESSED_SIZE as u32 + 1);
+    assert!(check_save_file(&raw, WorldDimensions::V0_1_X).is_err());
+}
+
+#[test]
+fn oversized_files_are_rejected() {
+    let mut raw = WORLD.to_vec();
+    raw.resize(MAX_FILE_SIZE + 1, 0);
+    assert_eq!(
+        check_header(&raw),
+        Err(LimitError::FileTooLarge {
+            size: MAX_FILE_SIZE + 1
+        })
+    );
+    assert!(get_world_info(&raw).is_none());
+    assert!(rename_world(&raw, "Renamed").is_none());
+}
+
+#[test]
+fn oversized_names_are_rejected() {
+    let long_name = "a".repeat(MAX_WORLD_NAME_SIZE + 1);
+    assert!(rename_world(WORLD, &long_name).is_none());
+
+    let name = "a".repeat(MAX_WORLD_NAME_SIZE);
+    let renamed = rename_world(WORLD, &name).unwrap();
+    assert_eq!(get_world_info(&renamed).unwrap().world_name, name);
+}
+
+#[test]
+fn too_many_chunks_are_rejected() {
+    assert_eq!(
+        check_save_file(WORLD, WorldDimensions::new(8, 8, 8)),
+        Err(LimitError::TooManyChunks { count: 512 })
+    );
+}
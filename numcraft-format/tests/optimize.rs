@@ -0,0 +1,128 @@
+/* The optimal encoder must be read by the decoder of the game, and optimizing a world must only change its size */
+
+use lz4_flex::{compress, decompress, decompress_size_prepended};
+use numcraft_format::{
+    dimensions::WorldDimensions,
+    format::{
+        compression::{compress_optimal, compress_optimal_prepend_size},
+        save_manager::optimize_world,
+    },
+    header::get_world_info,
+    numcraft_v0_1_0, numcraft_v0_1_3,
+};
+
+const V0_1_0_WORLDS: [&[u8]; 3] = [
+    include_bytes!("fixtures/v0_1_0_empty.ncw"),
+    include_bytes!("fixtures/v0_1_0_built.ncw"),
+    include_bytes!("fixtures/v0_1_0_max_inventory.ncw"),
+];
+
+const V0_1_3_WORLDS: [&[u8]; 3] = [
+    include_bytes!("fixtures/v0_1_3_empty.ncw"),
+    include_bytes!("fixtures/v0_1_3_built.ncw"),
+    include_bytes!("fixtures/v0_1_3_max_inventory.ncw"),
+];
+
+fn get_test_inputs() -> Vec<Vec<u8>> {
+    let mut noise = 1u32;
+    let random: Vec<u8> = (0..3000)
+        .map(|_| {
+            noise = noise.wrapping_mul(1103515245).wrapping_add(12345);
+            (noise >> 16) as u8
+        })
+        .collect();
+
+    let mut inputs = vec![
+        Vec::new(),
+        vec![7],
+        b"abcdabcdabcd".to_vec(),
+        vec![0; 100_000],
+        random.clone(),
+        random.iter().map(|b| b % 4).collect(),
+    ];
+    inputs.extend(V0_1_3_WORLDS.iter().map(|world| world.to_vec()));
+    let world = V0_1_3_WORLDS[1];
+    let world_info_size = u16::from_be_bytes([world[0], world[1]]) as usize;
+    inputs.push(decompress_size_prepended(&world[2 + world_info_size..]).unwrap());
+    inputs
+}
+
+#[test]
+fn optimal_streams_are_decoded_by_lz4_flex() {
+    for input in get_test_inputs() {
+        let compressed = compress_optimal(&input);
+        assert_eq!(decompress(&compressed, input.len()).unwrap(), input);
+
+        let prepended = compress_optimal_prepend_size(&input);
+        assert_eq!(decompress_size_prepended(&prepended).unwrap(), input);
+    }
+}
+
+#[test]
+fn optimal_streams_are_not_larger() {
+    for input in get_test_inputs() {
+        assert!(compress_optimal(&input).len() <= compress(&input).len());
+    }
+}
+
+/// Load a world and read back everything it saves, decompressed.
+macro_rules! get_content {
+    ($version:ident, $raw:expr) => {{
+        let mut save_manager = $version::save_manager::SaveManager::new();
+        save_manager.load_from_file($raw.to_vec()).unwrap();
+        let blocks: Vec<u8> = save_manager
+            .get_dimensions()
+            .iter_chunks()
+            .flat_map(|pos| {
+                save_manager
+                    .get_chunk_at_pos(pos)
+                    .unwrap()
+                    .get_all_blocks()
+                    .map(|b| b as u8)
+            })
+            .collect();
+        (
+            blocks,
+            postcard::to_allocvec(&save_manager.world_info).unwrap(),
+            postcard::to_allocvec(&save_manager.player_data).unwrap(),
+        )
+    }};
+}
+
+#[test]
+fn optimized_worlds_keep_their_content() {
+    for raw in V0_1_0_WORLDS {
+        let optimized = optimize_world(raw, WorldDimensions::V0_1_X).unwrap();
+        assert!(optimized.len() <= raw.len());
+        assert_eq!(
+            get_content!(numcraft_v0_1_0, optimized),
+            get_content!(numcraft_v0_1_0, raw)
+        );
+    }
+
+    for raw in V0_1_3_WORLDS {
+        let optimized = optimize_world(raw, WorldDimensions::V0_1_X).unwrap();
+        assert!(optimized.len() < raw.len());
+        assert_eq!(
+            get_content!(numcraft_v0_1_3, optimized),
+            get_content!(numcraft_v0_1_3, raw)
+        );
+        assert_eq!(
+            get_world_info(&optimized).unwrap().world_name,
+            get_world_info(raw).unwrap().world_name
+        );
+
+        // Nothing is left to gain
+        assert_eq!(
+            optimize_world(&optimized, WorldDimensions::V0_1_X).unwrap(),
+            optimized
+        );
+    }
+}
+
+#[test]
+fn broken_worlds_are_not_optimized() {
+    let raw = V0_1_3_WORLDS[1];
+    assert!(optimize_world(&raw[..raw.len() / 2], WorldDimensions::V0_1_X).is_none());
+    assert!(optimize_world(&[], WorldDimensions::V0_1_X).is_none());
+}
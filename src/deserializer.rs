@@ -1,17 +1,21 @@
+use numcraft_format::header::SaveVersion;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
-pub enum GameMode {
-    Survival,
-    Creative,
-}
+use crate::world_converter::{dimensions::WorldDimensions, fork::get_fork};
+
+pub use numcraft_format::{format::save_manager::GameMode, header::rename_world};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
 pub enum WorldVersion {
     V0_0_7_,
     V0_1_0,
     V0_1_3,
-    UNKNOWN,
+    /// A fork loaded by the user, with the `world_version` of its save files
+    Custom(u16),
+    /// Saved by a version of the game newer than the site, or by a fork that isn't loaded
+    Newer(u16),
+    /// A record whose header can't be read, listed so it can still be downloaded or deleted
+    Unreadable,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,7 +23,8 @@ pub enum UpdateStatus {
     AlreadyUpdated,
     CanBeUpdated,
     TooOld,
-    Unsupported,
+    TooNew,
+    Unreadable,
 }
 
 impl WorldVersion {
@@ -28,7 +33,14 @@ impl WorldVersion {
             WorldVersion::V0_0_7_ => "v0.0.7/8/9".to_string(),
             WorldVersion::V0_1_0 => "v0.1.0".to_string(),
             WorldVersion::V0_1_3 => "v0.1.3".to_string(),
-            WorldVersion::UNKNOWN => "unknown".to_string(),
+            WorldVersion::Custom(world_version) => match get_fork(world_version) {
+                Some(fork) => format!("{} (fork)", fork.name),
+                None => format!("fork {world_version}"),
+            },
+            WorldVersion::Newer(world_version) => {
+                format!("newer than supported (world version {world_version})")
+            }
+            WorldVersion::Unreadable => "unknown".to_string(),
         }
     }
 
@@ -37,7 +49,32 @@ impl WorldVersion {
             WorldVersion::V0_0_7_ => UpdateStatus::TooOld,
             WorldVersion::V0_1_0 => UpdateStatus::CanBeUpdated,
             WorldVersion::V0_1_3 => UpdateStatus::AlreadyUpdated,
-            WorldVersion::UNKNOWN => UpdateStatus::Unsupported,
+            WorldVersion::Custom(_) => UpdateStatus::CanBeUpdated,
+            WorldVersion::Newer(_) => UpdateStatus::TooNew,
+            WorldVersion::Unreadable => UpdateStatus::Unreadable,
+        }
+    }
+
+    /// Why the content of the world can't be edited or converted, if it can't.
+    pub fn get_content_blocked_reason(&self) -> Option<String> {
+        match *self {
+            WorldVersion::Newer(world_version) => Some(format!(
+                "This world was saved with the world version {world_version}, which is newer than this site supports. It can still be downloaded, renamed, backed up or deleted, but changing its content could corrupt it. If it comes from a custom build, load the description of the build below."
+            )),
+            WorldVersion::Unreadable => Some(
+                "The header of this world can't be read. It can still be downloaded or deleted.".to_string(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// The chunks saved in the world, when the site knows the layout of the version. Forks keep the v0.1.3 layout.
+    pub fn get_dimensions(&self) -> Option<WorldDimensions> {
+        match *self {
+            WorldVersion::V0_1_0 | WorldVersion::V0_1_3 | WorldVersion::Custom(_) => {
+                Some(WorldDimensions::V0_1_X)
+            }
+            WorldVersion::V0_0_7_ | WorldVersion::Newer(_) | WorldVersion::Unreadable => None,
         }
     }
 }
@@ -50,74 +87,27 @@ pub struct WorldInfo {
     pub gamemode: GameMode,
 }
 
-// v0.1.3
-#[derive(Serialize, Deserialize, Debug)]
-pub struct WorldInfo3 {
-    pub world_version: u16,
-    pub world_name: String,
-    pub world_seed: i32,
-    pub gamemode: GameMode,
-}
-
-// v0.1.0
-#[derive(Serialize, Deserialize, Debug)]
-pub struct WorldInfo2 {
-    pub world_name: String,
-    pub world_seed: i32,
-    pub gamemode: GameMode,
-}
-
-// v0.0.7 - v0.0.9
-#[derive(Serialize, Deserialize, Debug)]
-pub struct WorldInfo1 {
-    pub world_name: String,
-    pub world_seed: i32,
-}
-
 fn get_version_from_version_number(version_number: u16) -> WorldVersion {
     match version_number {
         0 => WorldVersion::V0_1_3,
-        _ => WorldVersion::UNKNOWN,
+        _ if get_fork(version_number).is_some() => WorldVersion::Custom(version_number),
+        _ => WorldVersion::Newer(version_number),
     }
 }
 
-pub fn get_world_info(raw: &Vec<u8>) -> Option<WorldInfo> {
-    if raw.len() < 2 {
-        return None;
-    }
-
-    let world_info_size = u16::from_be_bytes([raw[0], raw[1]]);
+/// Read the header of a world, recognising the forks loaded by the user.
+pub fn get_world_info(raw: &[u8]) -> Option<WorldInfo> {
+    let header = numcraft_format::header::get_world_info(raw)?;
 
-    if raw.len() < (2 + world_info_size) as usize {
-        return None;
-    }
-
-    // Try different versions
-    if let Ok(world_info) =
-        postcard::from_bytes::<WorldInfo3>(raw.get(2..(2 + world_info_size as usize))?)
-    {
-        Some(WorldInfo {
-            world_version: get_version_from_version_number(world_info.world_version),
-            world_name: world_info.world_name,
-            gamemode: world_info.gamemode,
-        })
-    } else if let Ok(world_info) =
-        postcard::from_bytes::<WorldInfo2>(raw.get(2..(2 + world_info_size as usize))?)
-    {
-        Some(WorldInfo {
-            world_version: WorldVersion::V0_1_0,
-            world_name: world_info.world_name,
-            gamemode: world_info.gamemode,
-        })
-    } else if let Ok(world_info) =
-        postcard::from_bytes::<WorldInfo1>(raw.get(2..(2 + world_info_size as usize))?)
-    {
-        Some(WorldInfo {
-            world_version: WorldVersion::V0_0_7_,
-            world_name: world_info.world_name,
-            gamemode: GameMode::Creative,
-        })
-    } else {
-        None
-    }
+    Some(WorldInfo {
+        world_version: match header.save_version {
+            SaveVersion::V0_0_7_ => WorldVersion::V0_0_7_,
+            SaveVersion::V0_1_0 => WorldVersion::V0_1_0,
+            SaveVersion::Numbered(version_number) => {
+                get_version_from_version_number(version_number)
+            }
+        },
+        world_name: header.world_name,
+        gamemode: header.gamemode,
+    })
 }
@@ -1,14 +1,20 @@
 use dioxus::{logger::{self, tracing}, prelude::*};
+use numcraft_format::limits::{check_header, LimitError, MAX_FILE_SIZE};
 
 use crate::{
     components::alert_dialog::{self, *},
-    deserializer::{UpdateStatus, WorldInfo},
+    deserializer::{rename_world, GameMode, UpdateStatus, WorldInfo, WorldVersion},
+    world_editor::{metadata::check_world_name, World},
+    world_converter::fork::{convert_from_fork, get_fork},
+    world_page::{BatchScriptPanel, ForkPanel, InspectorPanel, OpenedWorld, OptimizePanel, WorldPage},
 };
 
 mod components;
 mod deserializer;
 mod js_utils;
 mod world_converter;
+mod world_editor;
+mod world_page;
 
 const CSS: Asset = asset!("/assets/main.css");
 
@@ -22,6 +28,10 @@ const UPLOAD_ICON_SVG: Asset = asset!("/assets/upload.svg");
 const DELETE_ICON_SVG: Asset = asset!("/assets/delete.svg");
 const UPDATE_ICON_SVG: Asset = asset!("/assets/update.svg");
 const CANT_UPDATE_ICON_SVG: Asset = asset!("/assets/cant_update.svg");
+const EDIT_ICON_SVG: Asset = asset!("/assets/edit.svg");
+const CANT_EDIT_ICON_SVG: Asset = asset!("/assets/cant_edit.svg");
+const RENAME_ICON_SVG: Asset = asset!("/assets/rename.svg");
+const BACKUP_ICON_SVG: Asset = asset!("/assets/backup.svg");
 
 static LOGO: Asset = asset!("/assets/logo.svg");
 static CONNECT_CALCULATOR_SVG: Asset = asset!("/assets/connect_calculator.svg");
@@ -33,6 +43,8 @@ pub struct WorldRecord {
     pub world_data: Vec<u8>,
     pub world_info: WorldInfo,
     pub need_remove: bool,
+    /// Why the record can't be read as a world
+    pub problem: Option<String>,
 }
 
 fn fix_records_idexes(records: &mut Vec<WorldRecord>, start: usize) {
@@ -57,6 +69,23 @@ impl WorldRecord {
             world_data,
             world_info,
             need_remove,
+            problem: None,
+        }
+    }
+
+    /// A record that is listed only to be downloaded or deleted.
+    pub fn unreadable(record_index: usize, file_name: String, world_data: Vec<u8>, problem: String) -> Self {
+        WorldRecord {
+            record_index,
+            file_name,
+            world_data,
+            world_info: WorldInfo {
+                world_version: WorldVersion::Unreadable,
+                world_name: String::new(),
+                gamemode: GameMode::Survival,
+            },
+            need_remove: false,
+            problem: Some(problem),
         }
     }
 }
@@ -70,6 +99,7 @@ fn App() -> Element {
     let supported_browser = js_utils::is_usb_supported();
     let calculator_connected = use_signal(|| false);
     let worlds_list: Signal<Vec<WorldRecord>> = use_signal(Vec::new);
+    let opened_world: Signal<Option<OpenedWorld>> = use_signal(|| None);
 
     rsx! {
         document::Stylesheet { href: CSS }
@@ -81,7 +111,16 @@ fn App() -> Element {
         div { id: "page-body",
             if supported_browser {
                 ConnectPage { calculator_connected, worlds_list }
-                ListWorldsPage { calculator_connected, worlds_list }
+                if let Some(opened) = *opened_world.read() {
+                    WorldPage {
+                        calculator_connected,
+                        worlds_list,
+                        opened_world,
+                        opened,
+                    }
+                } else {
+                    ListWorldsPage { calculator_connected, worlds_list, opened_world }
+                }
             } else {
                 span { class: "connection-info",
                     "Your browser is not supported. Please use a Chromium based browser."
@@ -157,17 +196,60 @@ async fn update_worlds_list(
             .recv()
             .await
             .expect("An error occured during the download of the records.");
-        if let Some(world_info) = deserializer::get_world_info(&data) {
-            worlds_list
-                .write()
-                .push(WorldRecord::new(index, name, data, world_info, false));
-        } else {
-            tracing::warn!("Invalid world info detected.");
-        }
+        let record = match check_header(&data) {
+            Err(error) => WorldRecord::unreadable(index, name, data, error.get_description()),
+            Ok(()) => match deserializer::get_world_info(&data) {
+                Some(world_info) => WorldRecord::new(index, name, data, world_info, false),
+                None => WorldRecord::unreadable(index, name, data, "The header of the world is invalid.".to_string()),
+            },
+        };
+        worlds_list.write().push(record);
     }
     document::eval(format!("console.log('{:?}')", worlds_list).as_str());
 }
 
+/// Replace the data of a record on the calculator.
+async fn install_world_data(record_index: usize, data: Vec<u8>) {
+    let eval = document::eval(
+        format!(
+            r#"
+            var record = window.storage.records[{record_index}];
+            var data = await dioxus.recv();
+            var blob = new Blob([new Uint8Array(data)], {{
+                type: "application/octet-stream",
+            }});
+            record.data = blob;
+            await window.calculator.installStorage(window.storage, function () {{}});
+            return null;"#,
+        )
+        .as_str(),
+    );
+    eval.send(data).unwrap();
+    eval.await.unwrap();
+}
+
+/// Add a new record to the calculator.
+async fn add_world_data(name: String, data: Vec<u8>) {
+    let eval = document::eval(
+        format!(
+            r#"
+            var data = await dioxus.recv();
+            var blob = new Blob([new Uint8Array(data)], {{
+                type: "application/octet-stream",
+            }});
+            var name = "{}";
+            console.log(name);
+            window.storage.records.push({{name, type: "ncw", data: blob}});
+            await window.calculator.installStorage(window.storage, function () {{}});
+            return null;"#,
+            name,
+        )
+        .as_str(),
+    );
+    eval.send(data).unwrap();
+    eval.await.unwrap();
+}
+
 #[component]
 fn ConnectPage(
     calculator_connected: Signal<bool>,
@@ -205,15 +287,49 @@ fn get_next_filename(worlds_list: &Vec<WorldRecord>) -> Option<String> {
     None
 }
 
+/// Load a v0.1.3 world for the editor. The worlds of a fork are converted to the v0.1.3 ids.
+fn load_world_for_editing(record: &WorldRecord) -> Option<(World, Option<u16>)> {
+    let (data, fork_version) = match record.world_info.world_version {
+        WorldVersion::Custom(fork_version) => {
+            let fork = get_fork(fork_version)?;
+            let conversion = convert_from_fork(&record.world_data, &fork)?;
+            if conversion.replaced_blocks > 0 || conversion.removed_items > 0 {
+                tracing::info!(
+                    "{} blocks and {} items of the fork have no v0.1.3 equivalent. They are kept where the edits don't change them.",
+                    conversion.replaced_blocks,
+                    conversion.removed_items
+                );
+            }
+            (conversion.data, Some(fork_version))
+        }
+        _ => (record.world_data.clone(), None),
+    };
+
+    match World::load(&data) {
+        Ok(world) => Some((world, fork_version)),
+        Err(error) => {
+            tracing::warn!("Unable to open the world: {}", error.get_description());
+            None
+        }
+    }
+}
+
 #[component]
 fn ListWorldsPage(
     calculator_connected: Signal<bool>,
     worlds_list: Signal<Vec<WorldRecord>>,
+    opened_world: Signal<Option<OpenedWorld>>,
 ) -> Element {
     let mut open_delete_dialog = use_signal(|| false);
     let mut open_update_dialog = use_signal(|| false);
     let mut open_update_error = use_signal(|| false);
+    let mut open_load_error = use_signal(|| false);
+    let mut open_rename_dialog = use_signal(|| false);
+    let mut open_rename_error = use_signal(|| false);
+    let mut new_world_name = use_signal(String::new);
+    let mut upload_error: Signal<Option<String>> = use_signal(|| None);
     let mut selected_world: Signal<Option<usize>> = use_signal(|| None);
+    let mut selected_worlds: Signal<Vec<usize>> = use_signal(Vec::new);
     rsx!(
         div {
             id: "list-worlds-page-div",
@@ -229,11 +345,30 @@ fn ListWorldsPage(
                         key: "{i}",
                         class: "worlds-list-element",
                         class: if worlds_list.read()[i].need_remove { "removed-world-record" } else { "" },
+                        if matches!(worlds_list.read()[i].world_info.world_version, WorldVersion::V0_1_3) {
+                            input {
+                                class: "world-select-checkbox",
+                                r#type: "checkbox",
+                                title: "Select for the batch script",
+                                checked: selected_worlds.read().contains(&i),
+                                onchange: move |e| {
+                                    if e.checked() {
+                                        selected_worlds.write().push(i);
+                                    } else {
+                                        selected_worlds.write().retain(|selected| *selected != i);
+                                    }
+                                },
+                            }
+                        }
                         span { class: "worlds-file-name",
                             {format!("File Name: {}.ncw", worlds_list.read()[i].file_name)}
                         }
-                        span { class: "worlds-world-name",
-                            {format!("World Name: {}", worlds_list.read()[i].world_info.world_name)}
+                        if let Some(problem) = worlds_list.read()[i].problem.clone() {
+                            span { class: "worlds-world-name world-panel-error", {format!("Unreadable: {problem}")} }
+                        } else {
+                            span { class: "worlds-world-name",
+                                {format!("World Name: {}", worlds_list.read()[i].world_info.world_name)}
+                            }
                         }
                         span { class: "worlds-world-version",
                             {
@@ -281,6 +416,54 @@ fn ListWorldsPage(
                                 src: DELETE_ICON_SVG,
                             }
                         }
+                        if worlds_list.read()[i].problem.is_none() {
+                            a {
+                                onclick: move |_| {
+                                    selected_world.set(Some(i));
+                                    new_world_name.set(worlds_list.read()[i].world_info.world_name.clone());
+                                    open_rename_dialog.set(true)
+                                },
+                                title: "Rename",
+                                img {
+                                    class: "world-button-icon",
+                                    src: RENAME_ICON_SVG,
+                                }
+                            }
+                            a {
+                                onclick: move |_| async move {
+                                    let data = worlds_list.read()[i].world_data.clone();
+                                    let name = get_next_filename(&worlds_list.read()).expect("Unable to find a world name.");
+                                    add_world_data(name, data).await;
+                                    update_worlds_list(&mut calculator_connected, &mut worlds_list, false).await;
+                                },
+                                title: "Backup to a new file on the calculator",
+                                img {
+                                    class: "world-button-icon",
+                                    src: BACKUP_ICON_SVG,
+                                }
+                            }
+                        }
+                        if matches!(worlds_list.read()[i].world_info.world_version, WorldVersion::V0_1_3 | WorldVersion::Custom(_)) {
+                            a {
+                                onclick: move |_| {
+                                    match load_world_for_editing(&worlds_list.read()[i]) {
+                                        Some((world, fork_version)) => opened_world.set(Some(OpenedWorld::new(i, world, fork_version))),
+                                        None => open_load_error.set(true),
+                                    }
+                                },
+                                title: "Edit",
+                                img {
+                                    class: "world-button-icon",
+                                    src: EDIT_ICON_SVG,
+                                }
+                            }
+                        } else if let Some(reason) = worlds_list.read()[i].world_info.world_version.get_content_blocked_reason() {
+                            img {
+                                title: reason,
+                                class: "world-button-icon disabled-world-button-icon",
+                                src: CANT_EDIT_ICON_SVG,
+                            }
+                        }
                         if worlds_list.read()[i].world_info.world_version.get_update_supported()
                             == UpdateStatus::CanBeUpdated
                         {
@@ -303,8 +486,9 @@ fn ListWorldsPage(
                                     }
                                     UpdateStatus::CanBeUpdated => "",
                                     UpdateStatus::TooOld => "The world is too old to be updated.",
-                                    UpdateStatus::Unsupported => {
-                                        "This version is unsupported for updates. Custom build or very old version."
+                                    UpdateStatus::Unreadable => "The world can't be read, so it can't be updated.",
+                                    UpdateStatus::TooNew => {
+                                        "This world is newer than the latest version supported by the site. Custom build or newer version. Load the description of a custom build below."
                                     }
                                 },
                                 class: "world-button-icon",
@@ -318,32 +502,32 @@ fn ListWorldsPage(
                 async move {
                     let files = e.files();
                         for f in &files {
+                            // Check the size before the file is read
+                            if f.size() > MAX_FILE_SIZE as u64 {
+                                let error = LimitError::FileTooLarge { size: f.size() as usize };
+                                upload_error.set(Some(format!("{}: {}", f.name(), error.get_description())));
+                                continue;
+                            }
                             let data = f.read_bytes().await.expect("Unable to read the imported file.").to_vec();
+                            if let Err(error) = check_header(&data) {
+                                upload_error.set(Some(format!("{}: {}", f.name(), error.get_description())));
+                                continue;
+                            }
                             let name = get_next_filename(&worlds_list.read()).expect("Unable to find a world name.");
-                            let eval = document::eval(
-                                        format!(
-                                            r#"
-                                            var data = await dioxus.recv();
-                                            var blob = new Blob([new Uint8Array(data)], {{
-                                                type: "application/octet-stream",
-                                            }});
-                                            var name = "{}";
-                                            console.log(name);
-                                            window.storage.records.push({{name, type: "ncw", data: blob}});
-                                            await window.calculator.installStorage(window.storage, function () {{}});
-                                            return null;"#,
-                                            name,
-                                        )
-                                            .as_str(),
-                                    );
-                                    eval.send(data).unwrap();
-                                    eval.await.unwrap();
-                                    update_worlds_list(&mut calculator_connected, &mut worlds_list, false).await;
+                            add_world_data(name, data).await;
+                            update_worlds_list(&mut calculator_connected, &mut worlds_list, false).await;
                         }
                     }
                 }
                 }
             }
+
+            if !(*worlds_list.read()).is_empty() {
+                BatchScriptPanel { calculator_connected, worlds_list, selected_worlds }
+                OptimizePanel { calculator_connected, worlds_list }
+            }
+            ForkPanel { worlds_list }
+            InspectorPanel { worlds_list }
         }
 
         AlertDialogRoot {
@@ -360,6 +544,94 @@ fn ListWorldsPage(
             }
         }
 
+        AlertDialogRoot {
+            open: *open_load_error.read(),
+            on_open_change: move |v| open_load_error.set(v),
+            AlertDialogContent {
+                AlertDialogTitle { "Unable to open the world" }
+                AlertDialogDescription {
+                    "The world could not be read. It may be corrupted, the inspector below shows which part of the file is broken."
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "Ok" }
+                }
+            }
+        }
+
+        AlertDialogRoot {
+            open: upload_error.read().is_some(),
+            on_open_change: move |v: bool| {
+                if !v {
+                    upload_error.set(None)
+                }
+            },
+            AlertDialogContent {
+                AlertDialogTitle { "The world was not imported" }
+                AlertDialogDescription {
+                    {upload_error.read().clone().unwrap_or_default()}
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "Ok" }
+                }
+            }
+        }
+
+        AlertDialogRoot {
+            open: *open_rename_error.read(),
+            on_open_change: move |v| open_rename_error.set(v),
+            AlertDialogContent {
+                AlertDialogTitle { "Unable to rename the world" }
+                AlertDialogDescription {
+                    "The name of the world could not be read. It may be corrupted."
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "Ok" }
+                }
+            }
+        }
+
+        AlertDialogRoot {
+            open: *open_rename_dialog.read(),
+            on_open_change: move |v| open_rename_dialog.set(v),
+            AlertDialogContent {
+                AlertDialogTitle { "Rename the world" }
+                AlertDialogDescription {
+                    "Only the name saved in the world is changed, the rest of the file is kept as it is."
+                }
+                input {
+                    value: "{new_world_name}",
+                    oninput: move |e| new_world_name.set(e.value()),
+                }
+                if let Err(error) = check_world_name(&new_world_name.read()) {
+                    span { class: "world-panel-error", {error.get_description()} }
+                }
+                AlertDialogActions {
+                    AlertDialogCancel { "Cancel" }
+                    AlertDialogAction {
+                        on_click: move |_| async move {
+                            let world_index = (*selected_world.read()).expect("The page is broken.");
+                            if check_world_name(&new_world_name.read()).is_err() {
+                                return;
+                            }
+
+                            let renamed = worlds_list.read().get(world_index).and_then(|record| {
+                                rename_world(&record.world_data, &new_world_name.read())
+                                    .map(|data| (record.record_index, data))
+                            });
+                            if let Some((record_index, data)) = renamed {
+                                install_world_data(record_index, data).await;
+                            } else {
+                                open_rename_error.set(true);
+                            }
+                            selected_world.set(None);
+                            update_worlds_list(&mut calculator_connected, &mut worlds_list, false).await;
+                        },
+                        "Rename"
+                    }
+                }
+            }
+        }
+
         AlertDialogRoot {
             open: *open_delete_dialog.read(),
             on_open_change: move |v| open_delete_dialog.set(v),
@@ -382,6 +654,8 @@ fn ListWorldsPage(
                     AlertDialogAction {
                         on_click: move |_| async move {
                             let world_index = (*selected_world.read()).expect("The page is broken.");
+                            // The indexes of the worlds after the removed one change
+                            selected_worlds.set(Vec::new());
                             if let Some(record) = worlds_list.write().get_mut(world_index) {
                                 let record_index = record.record_index;
                                 record.need_remove = true;
@@ -414,10 +688,14 @@ fn ListWorldsPage(
                 AlertDialogDescription {
                     {
                         if let Some(index) = *selected_world.read() {
-                            format!(
+                            let mut description = format!(
                                 "You are about to update the world `{}` to the latest game version. You should backup your world before doing this. This action cannot be undone!",
                                 worlds_list.read()[index].world_info.world_name,
-                            )
+                            );
+                            if matches!(worlds_list.read()[index].world_info.world_version, WorldVersion::Custom(_)) {
+                                description += " The blocks of the fork that don't exist in the official game are replaced by their fallback or by air.";
+                            }
+                            description
                         } else {
                             "".to_string()
                         }
@@ -428,28 +706,15 @@ fn ListWorldsPage(
                     AlertDialogAction {
                         on_click: move |_| async move {
                             let world_index = (*selected_world.read()).expect("The page is broken.");
+                            // The list is read again from the calculator below, so the selection is rebuilt from scratch
+                            selected_worlds.set(Vec::new());
 
                             if let Some(record) = worlds_list.read().get(world_index) {
-                                if let Some(data) = world_converter::from_v0_1_0_to_0_1_3(
+                                if let Some(data) = world_converter::update_to_latest(
                                     &record.world_data,
+                                    record.world_info.world_version,
                                 ) {
-                                    let eval = document::eval(
-                                        format!(
-                                            r#"
-                                            var record = window.storage.records[{}];
-                                            var data = await dioxus.recv();
-                                            var blob = new Blob([new Uint8Array(data)], {{
-                                                type: "application/octet-stream",
-                                            }});
-                                            record.data = blob;
-                                            await window.calculator.installStorage(window.storage, function () {{}});
-                                            return null;"#,
-                                            record.record_index,
-                                        )
-                                            .as_str(),
-                                    );
-                                    eval.send(data).unwrap();
-                                    eval.await.unwrap();
+                                    install_world_data(record.record_index, data).await;
                                 } else {
                                     open_update_dialog.set(false);
                                     open_update_error.set(true);
@@ -0,0 +1,389 @@
+/* Custom builds of Numcraft that keep the v0.1.3 save format but have their own blocks and items */
+
+use std::{collections::BTreeMap, sync::Mutex};
+
+use nalgebra::Vector3;
+use numcraft_format::format::{
+    chunk::Chunk,
+    inventory::{Inventory, ItemStack},
+    save_manager::SaveManager,
+    FormatBlock, FormatItem, FormatVersion, CHUNK_SIZE,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::world_converter::{
+    numcraft_v0_1_3::{
+        constants::{save_manager::PLAYER_INVENTORY_SIZE, BlockType, ItemType},
+        inventory::ItemStack as OfficialItemStack,
+        registry::get_registry,
+        save_manager::{SaveManager as OfficialSaveManager, WorldInfo},
+        V0_1_3,
+    },
+    registry::{Registry, RegistryError},
+};
+
+/// The forks loaded by the user. A fork is recognised by the `world_version` of its save files.
+static FORKS: Mutex<Vec<ForkDescription>> = Mutex::new(Vec::new());
+
+/// A fork description, written in JSON or TOML. The ids of the blocks and items are the ids saved by the fork.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkDescription {
+    pub name: String,
+    /// The `world_version` written in the save files of the fork. 0 is the official v0.1.3.
+    pub world_version: u16,
+    #[serde(flatten)]
+    pub registry: Registry,
+    /// The official block used for a block of the fork that doesn't exist in v0.1.3, by name
+    #[serde(default)]
+    pub fallbacks: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ForkError {
+    Syntax(String),
+    Registry(RegistryError),
+    OfficialVersion,
+    UnknownFallback { block: String, fallback: String },
+}
+
+impl ForkError {
+    pub fn get_description(&self) -> String {
+        match self {
+            ForkError::Syntax(error) => format!("The fork description can't be read: {error}"),
+            ForkError::Registry(error) => error.get_description(),
+            ForkError::OfficialVersion => {
+                "The world version 0 is used by the official v0.1.3.".to_string()
+            }
+            ForkError::UnknownFallback { block, fallback } => format!(
+                "The fallback of \"{block}\" must be a v0.1.3 block, \"{fallback}\" is not."
+            ),
+        }
+    }
+}
+
+/// A fork world converted to the official v0.1.3 ids.
+pub struct ForkConversion {
+    pub data: Vec<u8>,
+    /// Blocks of the fork replaced by their fallback, or by air if they have none
+    pub replaced_blocks: usize,
+    /// Inventory slots emptied because the item doesn't exist in v0.1.3
+    pub removed_items: usize,
+}
+
+/// A block of a fork, kept as the id saved by the fork.
+#[derive(Clone, Copy, PartialEq)]
+struct ForkBlock(u8);
+
+impl FormatBlock for ForkBlock {
+    // Only used for new chunks, the chunks of a fork are always read from a file
+    const AIR: Self = ForkBlock(0);
+    const GRASS: Self = ForkBlock(2);
+
+    fn get_from_id(id: u8) -> Option<Self> {
+        Some(ForkBlock(id))
+    }
+
+    fn get_id(&self) -> u8 {
+        self.0
+    }
+}
+
+/// An item of a fork, kept as the id saved by the fork. It is saved like the items of v0.1.3.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct ForkItem(u32);
+
+impl FormatItem for ForkItem {
+    const AIR: Self = ForkItem(0);
+
+    // The stacks are only copied, the size given by the fork description is never needed
+    fn get_max_stack_amount(&self) -> u8 {
+        u8::MAX
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct ForkPlayerData {
+    pos: (f32, f32, f32),
+    rotation: (f32, f32),
+    inventory: Inventory<ForkItem>,
+}
+
+/// The v0.1.3 layout with the blocks and items of a fork.
+struct ForkFormat;
+
+impl FormatVersion for ForkFormat {
+    type Block = ForkBlock;
+    type WorldInfo = WorldInfo;
+    type PlayerData = ForkPlayerData;
+
+    fn new_world_info() -> Self::WorldInfo {
+        V0_1_3::new_world_info()
+    }
+
+    fn new_player_data() -> Self::PlayerData {
+        ForkPlayerData {
+            pos: (0., 0., 0.),
+            rotation: (0., 0.),
+            inventory: Inventory::new(PLAYER_INVENTORY_SIZE),
+        }
+    }
+}
+
+/// The chunks of a save manager that were saved. The chunks that were never generated are saved empty and stay so.
+fn get_saved_chunks<F: FormatVersion>(
+    save_manager: &SaveManager<F>,
+) -> Option<Vec<Option<Chunk<F::Block>>>> {
+    save_manager
+        .get_dimensions()
+        .iter_chunks()
+        .map(|pos| match save_manager.get_chunk_data_size(pos)? {
+            0 => Some(None),
+            _ => save_manager.get_chunk_at_pos(pos).ok().map(Some),
+        })
+        .collect()
+}
+
+/// A chunk with every block converted by `convert_block`.
+fn convert_chunk<A: FormatBlock, B: FormatBlock>(
+    chunk: &Chunk<A>,
+    mut convert_block: impl FnMut(usize, A) -> B,
+) -> Chunk<B> {
+    let mut converted = Chunk::new(*chunk.get_pos());
+    for x in 0..CHUNK_SIZE {
+        for y in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                let index = x + y * CHUNK_SIZE + z * CHUNK_SIZE * CHUNK_SIZE;
+                let pos = Vector3::new(x, y, z);
+                let block = chunk.get_at_unchecked(pos.map(|c| c as isize));
+                converted.set_at(pos, convert_block(index, block));
+            }
+        }
+    }
+    converted
+}
+
+/// Parse a fork description. JSON descriptions start with '{', anything else is read as TOML.
+pub fn parse_fork_description(text: &str) -> Result<ForkDescription, ForkError> {
+    let fork: ForkDescription = if text.trim_start().starts_with('{') {
+        serde_json::from_str(text).map_err(|error| ForkError::Syntax(error.to_string()))?
+    } else {
+        toml::from_str(text).map_err(|error| ForkError::Syntax(error.to_string()))?
+    };
+
+    if fork.world_version == 0 {
+        return Err(ForkError::OfficialVersion);
+    }
+    fork.registry.check().map_err(ForkError::Registry)?;
+
+    for (block, fallback) in &fork.fallbacks {
+        if fork.registry.get_block_by_name(block).is_none() {
+            return Err(ForkError::Registry(RegistryError::UnknownBlock(
+                block.clone(),
+            )));
+        }
+        if get_registry().get_block_by_name(fallback).is_none() {
+            return Err(ForkError::UnknownFallback {
+                block: block.clone(),
+                fallback: fallback.clone(),
+            });
+        }
+    }
+
+    Ok(fork)
+}
+
+/// Add a fork, replacing the fork with the same world version.
+pub fn install_fork(fork: ForkDescription) {
+    let mut forks = FORKS.lock().unwrap();
+    forks.retain(|f| f.world_version != fork.world_version);
+    forks.push(fork);
+}
+
+pub fn remove_fork(world_version: u16) {
+    FORKS
+        .lock()
+        .unwrap()
+        .retain(|f| f.world_version != world_version);
+}
+
+pub fn get_fork(world_version: u16) -> Option<ForkDescription> {
+    FORKS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|f| f.world_version == world_version)
+        .cloned()
+}
+
+pub fn get_forks() -> Vec<ForkDescription> {
+    FORKS.lock().unwrap().clone()
+}
+
+impl ForkDescription {
+    /// The v0.1.3 block matching a block of the fork, by name or through its fallback.
+    fn get_official_block(&self, id: u8) -> Option<u8> {
+        let block = self.registry.get_block(id)?;
+        let name = self.fallbacks.get(&block.name).unwrap_or(&block.name);
+        Some(get_registry().get_block_by_name(name)?.id)
+    }
+
+    fn get_official_item(&self, id: u8) -> Option<u8> {
+        let item = self.registry.get_item(id)?;
+        Some(get_registry().get_item_by_name(&item.name)?.id)
+    }
+
+    fn get_air_block(&self) -> u8 {
+        self.registry.get_block_by_name("Air").map_or(0, |b| b.id)
+    }
+
+    fn get_air_item(&self) -> u8 {
+        self.registry.get_item_by_name("Air").map_or(0, |it| it.id)
+    }
+}
+
+/// Convert a world of the fork to the official v0.1.3 ids so it can be opened in the editor or updated.
+pub fn convert_from_fork(raw: &[u8], fork: &ForkDescription) -> Option<ForkConversion> {
+    let mut fork_save_manager = SaveManager::<ForkFormat>::new();
+    fork_save_manager.load_from_file(raw.to_vec()).ok()?;
+    let official = get_registry();
+
+    let block_table: Vec<Option<u8>> = (0..=u8::MAX)
+        .map(|id| fork.get_official_block(id))
+        .collect();
+    let item_table: Vec<Option<u8>> = (0..=u8::MAX).map(|id| fork.get_official_item(id)).collect();
+
+    let mut save_manager = OfficialSaveManager::new();
+    save_manager.world_info = fork_save_manager.world_info.clone();
+    save_manager.world_info.world_version = 0;
+    save_manager.player_data.pos = fork_save_manager.player_data.pos;
+    save_manager.player_data.rotation = fork_save_manager.player_data.rotation;
+    save_manager.player_data.inventory.modified = fork_save_manager.player_data.inventory.modified;
+
+    let mut replaced_blocks = 0;
+    for chunk in get_saved_chunks(&fork_save_manager)?.iter().flatten() {
+        let converted = convert_chunk(chunk, |_, block| {
+            let same_name = fork
+                .registry
+                .get_block(block.0)
+                .is_some_and(|b| official.get_block_by_name(&b.name).is_some());
+            if !same_name {
+                replaced_blocks += 1;
+            }
+            block_table[block.0 as usize]
+                .and_then(BlockType::get_from_id)
+                .unwrap_or(BlockType::Air)
+        });
+        save_manager.set_chunk(&converted);
+    }
+
+    let mut removed_items = 0;
+    let inventory = &fork_save_manager.player_data.inventory;
+    for (i, slot) in inventory.get_all_slots().iter().enumerate() {
+        let item = usize::try_from(slot.get_item_type().0)
+            .ok()
+            .and_then(|id| item_table.get(id).copied().flatten())
+            .and_then(ItemType::get_from_id);
+        let converted = match item {
+            Some(item) => slot.with_item_type(item),
+            None => {
+                removed_items += 1;
+                ItemStack::void()
+            }
+        };
+        save_manager
+            .player_data
+            .inventory
+            .replace_slot_item_stack(i, converted);
+    }
+
+    Some(ForkConversion {
+        data: save_manager.get_raw(),
+        replaced_blocks,
+        removed_items,
+    })
+}
+
+/// Convert a world edited with the official ids back to the fork. The blocks and items of `original` that had no
+/// official equivalent are put back where the edit didn't change them.
+pub fn convert_to_fork(
+    raw: &[u8],
+    original: Option<&[u8]>,
+    fork: &ForkDescription,
+) -> Option<Vec<u8>> {
+    let mut official_save_manager = OfficialSaveManager::new();
+    official_save_manager.load_from_file(raw.to_vec()).ok()?;
+    let original = original.and_then(|original| {
+        let mut save_manager = SaveManager::<ForkFormat>::new();
+        save_manager.load_from_file(original.to_vec()).ok()?;
+        Some(save_manager)
+    });
+    let original_chunks = original.as_ref().and_then(get_saved_chunks);
+
+    let block_mapping = get_registry().get_block_id_mapping(&fork.registry);
+    let item_mapping = get_registry().get_item_id_mapping(&fork.registry);
+
+    let mut save_manager = SaveManager::<ForkFormat>::new();
+    save_manager.world_info = official_save_manager.world_info.clone();
+    save_manager.world_info.world_version = fork.world_version;
+    save_manager.player_data.pos = official_save_manager.player_data.pos;
+    save_manager.player_data.rotation = official_save_manager.player_data.rotation;
+    save_manager.player_data.inventory.modified =
+        official_save_manager.player_data.inventory.modified;
+
+    for (i, chunk) in get_saved_chunks(&official_save_manager)?.iter().enumerate() {
+        let Some(chunk) = chunk else {
+            continue;
+        };
+        let original_chunk = original_chunks.as_ref().and_then(|o| o.get(i)?.as_ref());
+        let converted = convert_chunk(chunk, |index, block| {
+            let kept = original_chunk
+                .map(|o| o.get_all_blocks()[index])
+                .filter(|original_block| {
+                    fork.get_official_block(original_block.0).unwrap_or(0) == block as u8
+                });
+            kept.unwrap_or_else(|| {
+                ForkBlock(
+                    block_mapping
+                        .get(block as u8)
+                        .unwrap_or_else(|| fork.get_air_block()),
+                )
+            })
+        });
+        save_manager.set_chunk(&converted);
+    }
+
+    let slots = official_save_manager.player_data.inventory.get_all_slots();
+    for (i, slot) in slots.iter().enumerate() {
+        let original_slot = original
+            .as_ref()
+            .and_then(|o| o.player_data.inventory.get_ref_to_slot(i).copied());
+        let converted = match original_slot {
+            Some(original_slot) => {
+                let official_item = u8::try_from(original_slot.get_item_type().0)
+                    .ok()
+                    .and_then(|id| fork.get_official_item(id));
+                match official_item {
+                    // The slot was emptied when the world was converted and nothing was put in it since
+                    None if *slot == OfficialItemStack::void() => Some(original_slot),
+                    Some(id) if id == slot.get_item_type() as u8 => {
+                        Some(slot.with_item_type(original_slot.get_item_type()))
+                    }
+                    _ => None,
+                }
+            }
+            None => None,
+        };
+        let converted = converted.unwrap_or_else(|| {
+            let item = item_mapping
+                .get(slot.get_item_type() as u8)
+                .unwrap_or_else(|| fork.get_air_item());
+            slot.with_item_type(ForkItem(item as u32))
+        });
+        save_manager
+            .player_data
+            .inventory
+            .replace_slot_item_stack(i, converted);
+    }
+
+    Some(save_manager.get_raw())
+}
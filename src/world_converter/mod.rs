@@ -2,31 +2,43 @@
 
 use nalgebra::Vector3;
 
-pub mod numcraft_v0_1_0;
-pub mod numcraft_v0_1_3;
+use crate::{
+    deserializer::WorldVersion,
+    world_converter::{
+        numcraft_v0_1_3::constants::world::CHUNK_SIZE,
+        transform::{
+            transform_block_pos_in_chunk, transform_chunk_pos, transform_player_pos,
+            transform_player_rotation,
+        },
+    },
+    world_editor::World,
+};
+
+pub mod fork;
+pub mod transform;
+
+// The save format lives in its own crate, shared with the game
+pub use numcraft_format::{dimensions, numcraft_v0_1_0, numcraft_v0_1_3, registry};
 
-pub fn from_v0_1_0_to_0_1_3(data: &Vec<u8>) -> Option<Vec<u8>> {
+pub fn from_v0_1_0_to_0_1_3(data: &[u8]) -> Option<Vec<u8>> {
     let mut save_manager_1 = numcraft_v0_1_0::save_manager::SaveManager::new();
     let mut save_manager_2 = numcraft_v0_1_3::save_manager::SaveManager::new();
 
-    save_manager_1.load_from_file(data).ok()?;
+    save_manager_1.load_from_file(data.to_vec()).ok()?;
 
-    save_manager_2.set_gamemode(
-        if save_manager_1.get_game_mode() == numcraft_v0_1_0::save_manager::GameMode::Creative {
-            numcraft_v0_1_3::save_manager::GameMode::Creative
-        } else {
-            numcraft_v0_1_3::save_manager::GameMode::Survival
-        },
-    );
+    // Blocks and items keep their name, not their id
+    let registry_1 = numcraft_v0_1_0::registry::get_registry();
+    let registry_2 = numcraft_v0_1_3::registry::get_registry();
+    let block_mapping = registry_1.get_block_id_mapping(registry_2);
+    let item_mapping = registry_1.get_item_id_mapping(registry_2);
+
+    save_manager_2.set_gamemode(save_manager_1.get_game_mode());
     save_manager_2.set_world_name(&save_manager_1.world_info.world_name);
     save_manager_2.set_world_seed(save_manager_1.world_info.world_seed);
 
-    save_manager_2.player_data.pos = (
-        4. * 8. - save_manager_1.player_data.pos.0,
-        4. * 8. - save_manager_1.player_data.pos.1,
-        save_manager_1.player_data.pos.2,
-    );
-    save_manager_1.player_data.rotation = save_manager_2.player_data.rotation;
+    save_manager_2.player_data.pos = transform_player_pos(save_manager_1.player_data.pos);
+    save_manager_2.player_data.rotation =
+        transform_player_rotation(save_manager_1.player_data.rotation);
     for slot in 0..save_manager_1.player_data.inventory.get_all_slots().len() {
         let old_item_stack: &numcraft_v0_1_0::inventory::ItemStack = save_manager_1
             .player_data
@@ -34,8 +46,9 @@ pub fn from_v0_1_0_to_0_1_3(data: &Vec<u8>) -> Option<Vec<u8>> {
             .get_ref_to_slot(slot)
             .unwrap();
         let new_item_stack = crate::world_converter::numcraft_v0_1_3::inventory::ItemStack::new(
-            numcraft_v0_1_3::constants::ItemType::get_from_id(old_item_stack.get_item_type() as u8)
-                .unwrap(),
+            numcraft_v0_1_3::constants::ItemType::get_from_id(
+                item_mapping.get(old_item_stack.get_item_type() as u8)?,
+            )?,
             old_item_stack.get_amount(),
             old_item_stack.creative_slot,
         );
@@ -45,35 +58,48 @@ pub fn from_v0_1_0_to_0_1_3(data: &Vec<u8>) -> Option<Vec<u8>> {
             .replace_slot_item_stack(slot, new_item_stack);
     }
 
-    for x in 0..4 {
-        for y in 0..4 {
-            for z in 0..4 {
-                let chunk1 = save_manager_1
-                    .get_chunk_at_pos(Vector3::new(x, y, z))
-                    .ok()?;
-                let old_pos = *chunk1.get_pos();
-                let mut chunk2 = crate::world_converter::numcraft_v0_1_3::chunk::Chunk::new(
-                    Vector3::new(3 - old_pos.x, 3 - old_pos.y, old_pos.z),
-                );
-                for bx in 0..8 {
-                    for by in 0..8 {
-                        for bz in 0..8 {
-                            let block_id = chunk1
-                                .get_at(Vector3::new(bx as isize, by as isize, bz as isize))
-                                .unwrap() as u8;
+    for chunk_pos in save_manager_1.get_dimensions().iter_chunks() {
+        let chunk1 = save_manager_1.get_chunk_at_pos(chunk_pos).ok()?;
+        let mut chunk2 = crate::world_converter::numcraft_v0_1_3::chunk::Chunk::new(
+            transform_chunk_pos(*chunk1.get_pos()),
+        );
+        for bx in 0..CHUNK_SIZE {
+            for by in 0..CHUNK_SIZE {
+                for bz in 0..CHUNK_SIZE {
+                    let block_id = chunk1
+                        .get_at(Vector3::new(bx as isize, by as isize, bz as isize))
+                        .unwrap() as u8;
 
-                            chunk2.set_at(
-                                Vector3::new(7 - bx, 7 - by, bz),
-                                numcraft_v0_1_3::constants::BlockType::get_from_id(block_id)
-                                    .unwrap(),
-                            );
-                        }
-                    }
+                    chunk2.set_at(
+                        transform_block_pos_in_chunk(Vector3::new(bx, by, bz)),
+                        numcraft_v0_1_3::constants::BlockType::get_from_id(
+                            block_mapping.get(block_id)?,
+                        )?,
+                    );
                 }
-                save_manager_2.set_chunk(&chunk2);
             }
         }
+        save_manager_2.set_chunk(&chunk2);
     }
 
-    Some(save_manager_2.get_raw())
+    // v0.1.0 has no border block and the mirrored player position may end up inside the terrain
+    let mut world = World::from_save_manager(save_manager_2).ok()?;
+    world.repair_border();
+    world.move_player_to_safe_spawn();
+
+    Some(world.get_raw())
+}
+
+/// Convert a world of an older version or of a loaded fork to v0.1.3.
+pub fn update_to_latest(data: &[u8], version: WorldVersion) -> Option<Vec<u8>> {
+    match version {
+        WorldVersion::V0_1_0 => from_v0_1_0_to_0_1_3(data),
+        WorldVersion::Custom(world_version) => {
+            Some(fork::convert_from_fork(data, &fork::get_fork(world_version)?)?.data)
+        }
+        _ => None,
+    }
 }
+
+#[cfg(test)]
+mod tests;
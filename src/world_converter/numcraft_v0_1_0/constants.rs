@@ -1,47 +0,0 @@
-use serde::{Deserialize, Serialize};
-
-pub mod world {
-    pub const CHUNK_SIZE: usize = 8; // MAX 8
-}
-
-#[derive(Clone, Copy, PartialEq, Eq)]
-pub enum BlockType {
-    Air = 0,
-    Stone = 1,
-    Grass = 2,
-    Dirt = 3,
-}
-
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
-#[repr(u8)]
-pub enum ItemType {
-    Air = 0,
-
-    StoneBlock = 1,
-    GrassBlock = 2,
-    DirtBlock = 3,
-}
-
-impl ItemType {
-    pub fn get_max_stack_amount(&self) -> u8 {
-        match *self {
-            ItemType::Air => 0,
-            ItemType::StoneBlock => 64,
-            ItemType::GrassBlock => 64,
-            ItemType::DirtBlock => 64,
-        }
-    }
-
-}
-
-impl BlockType {
-    pub const fn get_from_id(id: u8) -> Option<Self> {
-        match id {
-            0 => Some(BlockType::Air),
-            1 => Some(BlockType::Stone),
-            2 => Some(BlockType::Grass),
-            3 => Some(BlockType::Dirt),
-            _ => None,
-        }
-    }
-}
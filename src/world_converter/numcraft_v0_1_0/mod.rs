@@ -1,4 +0,0 @@
-pub mod chunk;
-pub mod save_manager;
-pub mod constants;
-pub mod inventory;
\ No newline at end of file
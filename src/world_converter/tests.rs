@@ -0,0 +1,459 @@
+/* Check the conversion of the v0.1.0 fixtures against their content, compare it with the last converted worlds, and
+check the conversions of forks. Run the tests with UPDATE_FIXTURES=1 to write the converted worlds again after a
+deliberate change of the converter. */
+
+use std::{env, f32::consts::PI, fs, path::PathBuf};
+
+use nalgebra::Vector3;
+use numcraft_format::format::chunk::Chunk;
+
+use crate::{
+    deserializer::{get_world_info, GameMode, WorldVersion},
+    world_converter::{
+        dimensions::WorldDimensions,
+        fork::{convert_from_fork, convert_to_fork, parse_fork_description, ForkError},
+        from_v0_1_0_to_0_1_3, numcraft_v0_1_0,
+        numcraft_v0_1_3::{
+            constants::{world::CHUNK_SIZE, BlockType, ItemType},
+            inventory::ItemStack,
+            save_manager::SaveManager,
+        },
+        registry::RegistryError,
+        transform::{
+            transform_block_pos, transform_block_pos_in_chunk, transform_chunk_pos,
+            transform_player_pos, transform_player_rotation,
+        },
+    },
+    world_editor::World,
+};
+
+const KINDS: [&str; 5] = ["empty", "built", "survival", "creative", "max_inventory"];
+
+fn get_fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("numcraft-format/tests/fixtures")
+}
+
+/// The expected worlds are written by the converter, so this only catches changes of the conversion.
+#[test]
+fn v0_1_0_conversion_is_unchanged() {
+    let fixtures = get_fixtures_dir();
+
+    for kind in KINDS {
+        let raw = fs::read(fixtures.join(format!("v0_1_0_{kind}.ncw"))).unwrap();
+        let converted = from_v0_1_0_to_0_1_3(&raw).unwrap();
+
+        let expected_path = fixtures.join(format!("converted/v0_1_0_{kind}.ncw"));
+        if env::var_os("UPDATE_FIXTURES").is_some() {
+            fs::create_dir_all(expected_path.parent().unwrap()).unwrap();
+            fs::write(&expected_path, &converted).unwrap();
+        }
+        let expected = fs::read(&expected_path).unwrap();
+        assert!(converted == expected, "The conversion of {kind} changed.");
+    }
+}
+
+#[test]
+fn converted_worlds_open_in_the_editor() {
+    let fixtures = get_fixtures_dir();
+
+    for kind in KINDS {
+        let raw = fs::read(fixtures.join(format!("v0_1_0_{kind}.ncw"))).unwrap();
+        let converted = from_v0_1_0_to_0_1_3(&raw).unwrap();
+
+        let world_info = get_world_info(&converted).unwrap();
+        assert!(matches!(world_info.world_version, WorldVersion::V0_1_3));
+        assert_eq!(world_info.world_name, format!("v0.1.0 {kind}"));
+        let expected_game_mode = if kind == "creative" {
+            GameMode::Creative
+        } else {
+            GameMode::Survival
+        };
+        assert_eq!(world_info.gamemode, expected_game_mode);
+
+        assert!(World::load(&converted).is_ok(), "{kind}");
+    }
+}
+
+/// The v0.1.3 block with the same name, written by hand so it doesn't depend on the registries used by the converter.
+fn get_expected_block(block: numcraft_v0_1_0::constants::BlockType) -> BlockType {
+    use numcraft_v0_1_0::constants::BlockType as OldBlockType;
+
+    match block {
+        OldBlockType::Air => BlockType::Air,
+        OldBlockType::Stone => BlockType::Stone,
+        OldBlockType::Grass => BlockType::Grass,
+        OldBlockType::Dirt => BlockType::Dirt,
+    }
+}
+
+fn get_expected_item(item: numcraft_v0_1_0::constants::ItemType) -> ItemType {
+    use numcraft_v0_1_0::constants::ItemType as OldItemType;
+
+    match item {
+        OldItemType::Air => ItemType::Air,
+        OldItemType::StoneBlock => ItemType::StoneBlock,
+        OldItemType::GrassBlock => ItemType::GrassBlock,
+        OldItemType::DirtBlock => ItemType::DirtBlock,
+    }
+}
+
+/// The expected worlds are read from the v0.1.0 fixtures, not from the converter: every block is mirrored on x and y,
+/// the sides and the floor become the border, the inventory is kept and the player is mirrored too, unless they would
+/// not stand safely there.
+#[test]
+fn converted_worlds_match_the_v0_1_0_fixtures() {
+    let fixtures = get_fixtures_dir();
+    let size_x = (WorldDimensions::V0_1_X.x * CHUNK_SIZE) as isize;
+    let size_y = (WorldDimensions::V0_1_X.y * CHUNK_SIZE) as isize;
+    let size_z = (WorldDimensions::V0_1_X.z * CHUNK_SIZE) as isize;
+
+    for kind in KINDS {
+        let raw = fs::read(fixtures.join(format!("v0_1_0_{kind}.ncw"))).unwrap();
+        let mut old_save_manager = numcraft_v0_1_0::save_manager::SaveManager::new();
+        old_save_manager.load_from_file(raw.clone()).unwrap();
+        let world = World::load(&from_v0_1_0_to_0_1_3(&raw).unwrap()).unwrap();
+
+        for chunk_pos in old_save_manager.get_dimensions().iter_chunks() {
+            let chunk = old_save_manager.get_chunk_at_pos(chunk_pos).unwrap();
+            for x in 0..CHUNK_SIZE as isize {
+                for y in 0..CHUNK_SIZE as isize {
+                    for z in 0..CHUNK_SIZE as isize {
+                        let block = chunk.get_at(Vector3::new(x, y, z)).unwrap();
+                        let pos = chunk_pos * CHUNK_SIZE as isize + Vector3::new(x, y, z);
+                        let mirrored = Vector3::new(size_x - 1 - pos.x, size_y - 1 - pos.y, pos.z);
+                        let is_border = mirrored.x == 0
+                            || mirrored.x == size_x - 1
+                            || mirrored.z == 0
+                            || mirrored.z == size_z - 1
+                            || mirrored.y == 0;
+                        let expected = if is_border {
+                            BlockType::Border
+                        } else {
+                            get_expected_block(block)
+                        };
+                        assert_eq!(world.get_block(mirrored), Some(expected), "{kind} {pos:?}");
+                    }
+                }
+            }
+        }
+        assert!(world.get_block(Vector3::new(0, 0, size_z)).is_none());
+
+        let old_slots = old_save_manager.player_data.inventory.get_all_slots();
+        let slots = world.get_inventory().get_all_slots();
+        assert_eq!(slots.len(), old_slots.len(), "{kind}");
+        for (slot, old_slot) in slots.iter().zip(old_slots) {
+            assert_eq!(
+                slot.get_item_type(),
+                get_expected_item(old_slot.get_item_type()),
+                "{kind}"
+            );
+            assert_eq!(slot.get_amount(), old_slot.get_amount(), "{kind}");
+            assert_eq!(slot.creative_slot, old_slot.creative_slot, "{kind}");
+        }
+
+        let (x, y, z) = old_save_manager.player_data.pos;
+        let mirrored = (size_x as f32 - x, size_y as f32 - y, z);
+        let mirrored_feet =
+            Vector3::new(mirrored.0, mirrored.1, mirrored.2).map(|c| c.floor() as isize);
+        let (x, y, z) = world.save_manager.player_data.pos;
+        let feet = Vector3::new(x, y, z).map(|c| c.floor() as isize);
+        assert!(world.is_safe_spawn(feet), "{kind}");
+        if world.is_safe_spawn(mirrored_feet) {
+            assert_eq!(world.save_manager.player_data.pos, mirrored, "{kind}");
+        }
+    }
+}
+
+/// The direction the player looks at, with a yaw of 0 looking along z.
+fn get_view_direction(rotation: (f32, f32)) -> Vector3<f32> {
+    let (pitch, yaw) = rotation;
+    Vector3::new(
+        pitch.cos() * yaw.sin(),
+        -pitch.sin(),
+        pitch.cos() * yaw.cos(),
+    )
+}
+
+fn assert_same_direction(a: Vector3<f32>, b: Vector3<f32>) {
+    assert!((a - b).norm() < 1e-5, "{a:?} != {b:?}");
+}
+
+#[test]
+fn chunk_and_block_transforms_agree() {
+    let chunk_size = CHUNK_SIZE as isize;
+
+    for chunk_pos in WorldDimensions::V0_1_X.iter_chunks() {
+        for x in 0..CHUNK_SIZE {
+            for y in 0..CHUNK_SIZE {
+                for z in 0..CHUNK_SIZE {
+                    let pos_in_chunk = Vector3::new(x, y, z);
+                    let world_pos = chunk_pos * chunk_size + pos_in_chunk.map(|c| c as isize);
+
+                    let transformed = transform_chunk_pos(chunk_pos) * chunk_size
+                        + transform_block_pos_in_chunk(pos_in_chunk).map(|c| c as isize);
+                    assert_eq!(transformed, transform_block_pos(world_pos));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn transforms_are_their_own_inverse() {
+    let block = Vector3::new(3, 20, 6);
+    assert_eq!(transform_block_pos(transform_block_pos(block)), block);
+    assert_eq!(
+        transform_chunk_pos(transform_chunk_pos(Vector3::new(0, 1, 2))),
+        Vector3::new(0, 1, 2)
+    );
+
+    let pos = (21.5, 31., 12.25);
+    assert_eq!(transform_player_pos(transform_player_pos(pos)), pos);
+
+    for rotation in [(0.3, 1.2), (-1.5, 0.), (0., PI), (1., 5.9)] {
+        let back = transform_player_rotation(transform_player_rotation(rotation));
+        assert_same_direction(get_view_direction(back), get_view_direction(rotation));
+    }
+}
+
+#[test]
+fn the_player_stays_on_the_same_block() {
+    let block = Vector3::new(3, 20, 6);
+    let center = (3.5, 20.5, 6.5);
+
+    let transformed = transform_player_pos(center);
+    let transformed_block = transform_block_pos(block);
+    assert_eq!(
+        transformed,
+        (
+            transformed_block.x as f32 + 0.5,
+            transformed_block.y as f32 + 0.5,
+            transformed_block.z as f32 + 0.5
+        )
+    );
+}
+
+#[test]
+fn the_view_direction_is_turned_with_the_world() {
+    for rotation in [(0.3, 1.2), (-1.5, 0.), (0., PI), (1., 5.9), (0., 0.)] {
+        let direction = get_view_direction(rotation);
+        let expected = Vector3::new(-direction.x, -direction.y, direction.z);
+
+        let (pitch, yaw) = transform_player_rotation(rotation);
+        assert_same_direction(get_view_direction((pitch, yaw)), expected);
+        assert!((0. ..std::f32::consts::TAU).contains(&yaw));
+    }
+}
+
+#[test]
+fn a_marker_block_and_the_facing_survive_the_conversion() {
+    use numcraft_v0_1_0::{constants::BlockType as OldBlockType, save_manager::SaveManager};
+
+    let marker = Vector3::new(3, 20, 6);
+    // The floor of the v0.1.3 world is at y = 0, so it is at y = 31 before the conversion
+    let floor_y = (WorldDimensions::V0_1_X.y * CHUNK_SIZE - 1) as isize;
+    let old_player_pos = transform_player_pos((10.5, 1., 12.5));
+    let old_rotation = (0.4, 2.);
+
+    let mut save_manager = SaveManager::new();
+    save_manager.player_data.pos = old_player_pos;
+    save_manager.player_data.rotation = old_rotation;
+    for chunk_pos in save_manager.get_dimensions().iter_chunks() {
+        let mut chunk: Chunk<OldBlockType> = Chunk::new(chunk_pos);
+        for x in 0..CHUNK_SIZE {
+            for y in 0..CHUNK_SIZE {
+                for z in 0..CHUNK_SIZE {
+                    let world_pos = chunk_pos * CHUNK_SIZE as isize
+                        + Vector3::new(x as isize, y as isize, z as isize);
+                    if world_pos == marker {
+                        chunk.set_at(Vector3::new(x, y, z), OldBlockType::Stone);
+                    } else if world_pos.y == floor_y {
+                        chunk.set_at(Vector3::new(x, y, z), OldBlockType::Dirt);
+                    }
+                }
+            }
+        }
+        save_manager.set_chunk(&chunk);
+    }
+
+    let converted = from_v0_1_0_to_0_1_3(&save_manager.get_raw()).unwrap();
+    let world = World::load(&converted).unwrap();
+
+    assert_eq!(
+        world.get_block(transform_block_pos(marker)),
+        Some(BlockType::Stone)
+    );
+    assert_eq!(world.get_block(marker), Some(BlockType::Air));
+
+    let player_data = &world.save_manager.player_data;
+    assert_eq!(player_data.pos, transform_player_pos(old_player_pos));
+    assert_eq!(
+        player_data.rotation,
+        transform_player_rotation(old_rotation)
+    );
+
+    let direction = get_view_direction(old_rotation);
+    assert_same_direction(
+        get_view_direction(player_data.rotation),
+        Vector3::new(-direction.x, -direction.y, direction.z),
+    );
+}
+
+const FORK_JSON: &str = r#"{
+    "name": "Marble",
+    "world_version": 7,
+    "blocks": [
+        { "id": 0, "name": "Air" },
+        { "id": 1, "name": "Stone" },
+        { "id": 2, "name": "Marble" },
+        { "id": 3, "name": "Glass" },
+        { "id": 5, "name": "Dirt" }
+    ],
+    "items": [
+        { "id": 0, "name": "Air" },
+        { "id": 1, "name": "Stone Block" },
+        { "id": 2, "name": "Marble Block" },
+        { "id": 4, "name": "Dirt Block" }
+    ],
+    "fallbacks": { "Marble": "Stone" }
+}"#;
+
+const FORK_TOML: &str = r#"
+name = "Marble"
+world_version = 7
+
+[fallbacks]
+Marble = "Stone"
+
+[[blocks]]
+id = 0
+name = "Air"
+
+[[blocks]]
+id = 1
+name = "Stone"
+
+[[blocks]]
+id = 2
+name = "Marble"
+
+[[blocks]]
+id = 3
+name = "Glass"
+
+[[blocks]]
+id = 5
+name = "Dirt"
+
+[[items]]
+id = 0
+name = "Air"
+
+[[items]]
+id = 1
+name = "Stone Block"
+
+[[items]]
+id = 2
+name = "Marble Block"
+
+[[items]]
+id = 4
+name = "Dirt Block"
+"#;
+
+#[test]
+fn fork_descriptions_are_read_from_json_and_toml() {
+    let from_json = parse_fork_description(FORK_JSON).unwrap();
+    let from_toml = parse_fork_description(FORK_TOML).unwrap();
+
+    assert_eq!(from_json, from_toml);
+    assert_eq!(from_json.world_version, 7);
+    assert_eq!(from_json.fallbacks["Marble"], "Stone");
+    assert!(matches!(
+        parse_fork_description("name = "),
+        Err(ForkError::Syntax(_))
+    ));
+}
+
+#[test]
+fn invalid_fork_descriptions_are_rejected() {
+    let official = FORK_TOML.replace("world_version = 7", "world_version = 0");
+    assert_eq!(
+        parse_fork_description(&official),
+        Err(ForkError::OfficialVersion)
+    );
+
+    let unknown_fallback = FORK_TOML.replace("Marble = \"Stone\"", "Marble = \"Granite\"");
+    assert_eq!(
+        parse_fork_description(&unknown_fallback),
+        Err(ForkError::UnknownFallback {
+            block: "Marble".to_string(),
+            fallback: "Granite".to_string()
+        })
+    );
+
+    let unknown_block = FORK_TOML.replace("Marble = \"Stone\"", "Basalt = \"Stone\"");
+    assert_eq!(
+        parse_fork_description(&unknown_block),
+        Err(ForkError::Registry(RegistryError::UnknownBlock(
+            "Basalt".to_string()
+        )))
+    );
+}
+
+/// The blocks and items of the fork are written with the official types that have the same ids.
+fn get_fork_block(id: u8) -> BlockType {
+    BlockType::get_from_id(id).unwrap()
+}
+
+fn get_fork_item(id: u8) -> ItemType {
+    ItemType::get_from_id(id).unwrap()
+}
+
+#[test]
+fn fork_blocks_and_items_come_back_where_they_were_not_edited() {
+    let fork = parse_fork_description(FORK_JSON).unwrap();
+    let marble = Vector3::new(1, 1, 1);
+    let edited_marble = Vector3::new(2, 1, 1);
+    let glass = Vector3::new(3, 1, 1);
+    let dirt = Vector3::new(4, 1, 1);
+
+    let mut fork_world = World::new_empty(SaveManager::new());
+    fork_world.save_manager.world_info.world_version = fork.world_version;
+    fork_world.set_block(marble, get_fork_block(2));
+    fork_world.set_block(edited_marble, get_fork_block(2));
+    fork_world.set_block(glass, get_fork_block(3));
+    fork_world.set_block(dirt, get_fork_block(5));
+    let inventory = &mut fork_world.save_manager.player_data.inventory;
+    inventory.replace_slot_item_stack(0, ItemStack::new(get_fork_item(2), 3, false));
+    inventory.replace_slot_item_stack(1, ItemStack::new(get_fork_item(4), 7, false));
+    let fork_raw = fork_world.get_raw();
+
+    let conversion = convert_from_fork(&fork_raw, &fork).unwrap();
+    assert_eq!(conversion.replaced_blocks, 3);
+    assert_eq!(conversion.removed_items, 1);
+
+    let mut world = World::load(&conversion.data).unwrap();
+    assert_eq!(world.get_block(marble), Some(BlockType::Stone));
+    assert_eq!(world.get_block(glass), Some(BlockType::Air));
+    assert_eq!(world.get_block(dirt), Some(BlockType::Dirt));
+    let slots = world.get_inventory().get_all_slots();
+    assert_eq!(slots[0].get_item_type(), ItemType::Air);
+    assert_eq!(slots[1], ItemStack::new(ItemType::DirtBlock, 7, false));
+
+    world.set_block(edited_marble, BlockType::Dirt);
+    let back = convert_to_fork(&world.get_raw(), Some(&fork_raw), &fork).unwrap();
+
+    let mut save_manager = SaveManager::new();
+    save_manager.load_from_file(back).unwrap();
+    assert_eq!(save_manager.world_info.world_version, fork.world_version);
+    let back = World::from_save_manager(save_manager).unwrap();
+    assert_eq!(back.get_block(marble), Some(get_fork_block(2)));
+    assert_eq!(back.get_block(edited_marble), Some(get_fork_block(5)));
+    assert_eq!(back.get_block(glass), Some(get_fork_block(3)));
+    assert_eq!(back.get_block(dirt), Some(get_fork_block(5)));
+    let slots = back.get_inventory().get_all_slots();
+    assert_eq!(slots[0], ItemStack::new(get_fork_item(2), 3, false));
+    assert_eq!(slots[1], ItemStack::new(get_fork_item(4), 7, false));
+}
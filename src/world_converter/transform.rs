@@ -0,0 +1,48 @@
+/* The coordinates of v0.1.0 worlds, converted to v0.1.3. The world is turned by half a turn around the z axis: x and
+y are mirrored around the center of the world and z is kept, so the transform is its own inverse. */
+
+use std::f32::consts::TAU;
+
+use nalgebra::Vector3;
+
+use crate::world_converter::{
+    dimensions::WorldDimensions, numcraft_v0_1_3::constants::world::CHUNK_SIZE,
+};
+
+const DIMENSIONS: WorldDimensions = WorldDimensions::V0_1_X;
+
+const CHUNK_SIZE_I: isize = CHUNK_SIZE as isize;
+
+/// The position of a block in the world. Everything else is transformed around the same center.
+pub fn transform_block_pos(pos: Vector3<isize>) -> Vector3<isize> {
+    Vector3::new(
+        DIMENSIONS.x as isize * CHUNK_SIZE_I - 1 - pos.x,
+        DIMENSIONS.y as isize * CHUNK_SIZE_I - 1 - pos.y,
+        pos.z,
+    )
+}
+
+/// The chunk containing the transformed blocks of the chunk at `pos`.
+pub fn transform_chunk_pos(pos: Vector3<isize>) -> Vector3<isize> {
+    transform_block_pos(pos * CHUNK_SIZE_I).map(|c| c.div_euclid(CHUNK_SIZE_I))
+}
+
+/// The position of a block inside its chunk. The chunk itself moves with `transform_chunk_pos`.
+pub fn transform_block_pos_in_chunk(pos: Vector3<usize>) -> Vector3<usize> {
+    transform_block_pos(pos.map(|c| c as isize)).map(|c| c.rem_euclid(CHUNK_SIZE_I) as usize)
+}
+
+/// The player is mirrored around the same center as the blocks: the block at `x` covers `x..x + 1`.
+pub fn transform_player_pos(pos: (f32, f32, f32)) -> (f32, f32, f32) {
+    (
+        (DIMENSIONS.x * CHUNK_SIZE) as f32 - pos.0,
+        (DIMENSIONS.y * CHUNK_SIZE) as f32 - pos.1,
+        pos.2,
+    )
+}
+
+/// The rotation is (pitch, yaw) in radians, with a yaw of 0 looking along z. Turning the world around z mirrors the
+/// vertical and the horizontal part of the view direction.
+pub fn transform_player_rotation(rotation: (f32, f32)) -> (f32, f32) {
+    (-rotation.0, (-rotation.1).rem_euclid(TAU))
+}
@@ -0,0 +1,69 @@
+use nalgebra::Vector3;
+
+use crate::{world_converter::numcraft_v0_1_3::constants::BlockType, world_editor::World};
+
+/// The blocks replaced while rebuilding the border, with the block that was there before.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BorderRepairReport {
+    pub replaced: Vec<(Vector3<isize>, BlockType)>,
+}
+
+impl BorderRepairReport {
+    pub fn get_description(&self) -> String {
+        if self.replaced.is_empty() {
+            return "The border is intact.".to_string();
+        }
+
+        let mut counts: Vec<(BlockType, usize)> = Vec::new();
+        for (_, block_type) in &self.replaced {
+            match counts.iter_mut().find(|(b, _)| b == block_type) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((*block_type, 1)),
+            }
+        }
+
+        let details: Vec<String> = counts
+            .iter()
+            .map(|(block_type, count)| format!("{count} {block_type:?}"))
+            .collect();
+
+        format!(
+            "{} blocks were replaced by the border ({}).",
+            self.replaced.len(),
+            details.join(", ")
+        )
+    }
+}
+
+impl World {
+    /// The border covers the four sides and the floor of the world. The sky stays open.
+    pub fn is_border_position(&self, pos: Vector3<isize>) -> bool {
+        let size = self.get_size();
+        self.is_in_world(pos)
+            && (pos.x == 0
+                || pos.x == size.x - 1
+                || pos.z == 0
+                || pos.z == size.z - 1
+                || pos.y == 0)
+    }
+
+    /// Returns every border position that doesn't contain a `Border` block.
+    pub fn get_border_gaps(&self) -> Vec<Vector3<isize>> {
+        self.find_blocks(&self.get_bounds(), |pos, block_type| {
+            self.is_border_position(pos) && block_type != BlockType::Border
+        })
+    }
+
+    /// Fill every gap of the border.
+    pub fn repair_border(&mut self) -> BorderRepairReport {
+        let mut report = BorderRepairReport::default();
+
+        for pos in self.get_border_gaps() {
+            let old_block = self.get_block(pos).unwrap();
+            self.set_block(pos, BlockType::Border);
+            report.replaced.push((pos, old_block));
+        }
+
+        report
+    }
+}
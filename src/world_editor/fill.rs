@@ -0,0 +1,108 @@
+use nalgebra::Vector3;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::constants::BlockType,
+    world_editor::{BlockBox, Random, World, NEIGHBOURS},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplaceOptions {
+    /// Chance for each matching block to be replaced, between 0 and 1.
+    pub probability: f32,
+    pub seed: u32,
+    /// Only replace the blocks that touch air on at least one face.
+    pub only_exposed: bool,
+}
+
+impl Default for ReplaceOptions {
+    fn default() -> Self {
+        ReplaceOptions {
+            probability: 1.,
+            seed: 0,
+            only_exposed: false,
+        }
+    }
+}
+
+impl World {
+    /// Returns true if at least one face of the block touches air.
+    pub fn is_exposed(&self, pos: Vector3<isize>) -> bool {
+        NEIGHBOURS.iter().any(|offset| self.is_air(pos + offset))
+    }
+
+    /// Fill the box with the given block. Returns the number of blocks changed.
+    pub fn fill(&mut self, area: &BlockBox, block_type: BlockType) -> usize {
+        let Some(area) = area.intersection(&self.get_bounds()) else {
+            return 0;
+        };
+
+        area.iter()
+            .filter(|pos| self.set_block(*pos, block_type))
+            .count()
+    }
+
+    /// Replace every `from` block of the box by `to`. Returns the number of blocks changed.
+    pub fn replace(
+        &mut self,
+        area: &BlockBox,
+        from: BlockType,
+        to: BlockType,
+        options: &ReplaceOptions,
+    ) -> usize {
+        let Some(area) = area.intersection(&self.get_bounds()) else {
+            return 0;
+        };
+
+        let mut random = Random::new(options.seed);
+
+        // Select the blocks first so the exposure test is not affected by the blocks we replace
+        let selected: Vec<Vector3<isize>> = area
+            .iter()
+            .filter(|pos| self.get_block(*pos) == Some(from))
+            .filter(|pos| !options.only_exposed || self.is_exposed(*pos))
+            .filter(|_| options.probability >= 1. || random.next_f32() < options.probability)
+            .collect();
+
+        selected
+            .into_iter()
+            .filter(|pos| self.set_block(*pos, to))
+            .count()
+    }
+
+    /// Turn the box into a shell of `block_type` filled with air. Returns the number of blocks changed.
+    pub fn hollow(&mut self, area: &BlockBox, block_type: BlockType) -> usize {
+        let Some(area) = area.intersection(&self.get_bounds()) else {
+            return 0;
+        };
+
+        area.iter()
+            .filter(|pos| {
+                let on_shell = pos.x == area.min.x
+                    || pos.x == area.max.x
+                    || pos.y == area.min.y
+                    || pos.y == area.max.y
+                    || pos.z == area.min.z
+                    || pos.z == area.max.z;
+
+                self.set_block(*pos, if on_shell { block_type } else { BlockType::Air })
+            })
+            .count()
+    }
+
+    /// Draw the four vertical sides of the box, without floor and ceiling. Returns the number of blocks changed.
+    pub fn walls(&mut self, area: &BlockBox, block_type: BlockType) -> usize {
+        let Some(area) = area.intersection(&self.get_bounds()) else {
+            return 0;
+        };
+
+        area.iter()
+            .filter(|pos| {
+                pos.x == area.min.x
+                    || pos.x == area.max.x
+                    || pos.z == area.min.z
+                    || pos.z == area.max.z
+            })
+            .filter(|pos| self.set_block(*pos, block_type))
+            .count()
+    }
+}
@@ -0,0 +1,224 @@
+use postcard::{from_bytes, to_allocvec};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    world_converter::numcraft_v0_1_3::save_manager::{PlayerData, WorldInfo},
+    world_editor::{BlockChange, World},
+};
+
+/// The world data that isn't stored in the chunks.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WorldState {
+    pub world_info: WorldInfo,
+    pub player_data: PlayerData,
+}
+
+/// A reversible edit of the world.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EditCommand {
+    pub description: String,
+    pub changes: Vec<BlockChange>,
+    /// The world info and the player data before and after the edit, if they changed
+    pub state: Option<(WorldState, WorldState)>,
+}
+
+/// The undo/redo stack of a world.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct EditHistory {
+    /// Checksum of the save file the first command applies to
+    base_checksum: u32,
+    commands: Vec<EditCommand>,
+    /// Number of commands currently applied to the world
+    position: usize,
+}
+
+/// FNV-1a hash, used to make sure a saved history matches the world it is restored on.
+pub fn get_checksum(data: &[u8]) -> u32 {
+    data.iter().fold(0x811C_9DC5, |hash, byte| {
+        (hash ^ *byte as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+impl EditHistory {
+    pub fn new(base_checksum: u32) -> Self {
+        EditHistory {
+            base_checksum,
+            commands: Vec::new(),
+            position: 0,
+        }
+    }
+
+    pub fn get_commands(&self) -> &Vec<EditCommand> {
+        &self.commands
+    }
+
+    pub fn get_position(&self) -> usize {
+        self.position
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.position > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.position < self.commands.len()
+    }
+
+    /// Add a command after the current position. The undone commands are forgotten.
+    fn push(&mut self, command: EditCommand) {
+        self.commands.truncate(self.position);
+        self.commands.push(command);
+        self.position += 1;
+    }
+
+    /// Encode the history so it can be put in the local storage of the browser.
+    pub fn to_storage_string(&self) -> String {
+        to_allocvec(self)
+            .unwrap()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    pub fn from_storage_string(value: &str) -> Option<Self> {
+        if !value.len().is_multiple_of(2) {
+            return None;
+        }
+
+        let bytes = (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+
+        from_bytes(&bytes).ok()
+    }
+}
+
+impl World {
+    pub fn get_history(&self) -> &EditHistory {
+        &self.history
+    }
+
+    /// Forget every command. The current world becomes the base of the history.
+    pub fn reset_history(&mut self, base_checksum: u32) {
+        self.history = EditHistory::new(base_checksum);
+    }
+
+    fn get_state(&self) -> WorldState {
+        WorldState {
+            world_info: self.save_manager.world_info.clone(),
+            player_data: self.save_manager.player_data.clone(),
+        }
+    }
+
+    fn set_state(&mut self, state: &WorldState) {
+        self.save_manager.world_info = state.world_info.clone();
+        self.save_manager.player_data = state.player_data.clone();
+    }
+
+    /// Run `edit` and add the blocks and the state it changed to the history.
+    pub(super) fn record_command<R>(
+        &mut self,
+        description: &str,
+        edit: impl FnOnce(&mut World) -> R,
+    ) -> R {
+        let state_before = self.get_state();
+        self.start_recording_changes();
+
+        let result = edit(self);
+
+        let changes = self.take_recorded_changes();
+        let state_after = self.get_state();
+        let state_changed = to_allocvec(&state_before).ok() != to_allocvec(&state_after).ok();
+
+        if !changes.is_empty() || state_changed {
+            self.history.push(EditCommand {
+                description: description.to_string(),
+                changes,
+                state: state_changed.then_some((state_before, state_after)),
+            });
+        }
+
+        result
+    }
+
+    /// Edit the inventory, the player or the world info. The edit can be undone.
+    pub fn edit_state<R>(&mut self, description: &str, edit: impl FnOnce(&mut World) -> R) -> R {
+        self.record_command(description, edit)
+    }
+
+    pub fn undo(&mut self) -> bool {
+        if !self.history.can_undo() {
+            return false;
+        }
+
+        self.history.position -= 1;
+        let command = self.history.commands[self.history.position].clone();
+        self.revert_changes(&command.changes);
+        if let Some((before, _)) = &command.state {
+            self.set_state(before);
+        }
+
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        if !self.history.can_redo() {
+            return false;
+        }
+
+        let command = self.history.commands[self.history.position].clone();
+        for change in &command.changes {
+            self.set_block(change.pos, change.new_block);
+        }
+        if let Some((_, after)) = &command.state {
+            self.set_state(after);
+        }
+        self.history.position += 1;
+
+        true
+    }
+
+    /// Undo or redo commands until `position` commands are applied.
+    pub fn go_to_history_position(&mut self, position: usize) {
+        while self.history.position > position && self.undo() {}
+        while self.history.position < position && self.redo() {}
+    }
+
+    /// Run `edit` then put the world back as it was. The history is restored too, so the commands that were undone
+    /// before can still be redone.
+    pub fn dry_run<R>(&mut self, edit: impl FnOnce(&mut World) -> R) -> R {
+        let history = self.history.clone();
+
+        let result = edit(self);
+
+        self.go_to_history_position(history.position);
+        self.history = history;
+
+        result
+    }
+
+    /// Replay a history saved before a page reload. The history is only restored if it was made on the same save
+    /// file and nothing was edited since the world was loaded.
+    pub fn restore_history(&mut self, history: EditHistory) -> bool {
+        if history.base_checksum != self.history.base_checksum
+            || !self.history.is_empty()
+            || history.position > history.commands.len()
+        {
+            return false;
+        }
+
+        let position = history.position;
+        self.history = EditHistory {
+            position: 0,
+            ..history
+        };
+        self.go_to_history_position(position);
+
+        true
+    }
+}
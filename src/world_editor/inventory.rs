@@ -0,0 +1,159 @@
+use crate::{
+    world_converter::numcraft_v0_1_3::{
+        constants::ItemType,
+        inventory::{Inventory, ItemStack},
+        save_manager::GameMode,
+    },
+    world_editor::World,
+};
+
+/// Every item that can be put in an inventory.
+pub fn get_item_types() -> impl Iterator<Item = ItemType> {
+    (1..=u8::MAX).filter_map(ItemType::get_from_id)
+}
+
+impl World {
+    pub fn get_inventory(&self) -> &Inventory {
+        &self.save_manager.player_data.inventory
+    }
+
+    fn get_inventory_mut(&mut self) -> &mut Inventory {
+        &mut self.save_manager.player_data.inventory
+    }
+
+    /// Slots are infinite in Creative mode.
+    fn is_creative(&self) -> bool {
+        self.save_manager.get_game_mode() == GameMode::Creative
+    }
+
+    /// Put `amount` items in the slot. The amount is capped at the max stack amount of the item.
+    /// An amount of 0 empties the slot.
+    pub fn set_inventory_slot(
+        &mut self,
+        slot: usize,
+        item_type: ItemType,
+        amount: u8,
+    ) -> Option<()> {
+        let amount = amount.min(item_type.get_max_stack_amount());
+        let item_stack = if amount == 0 {
+            ItemStack::void()
+        } else {
+            ItemStack::new(item_type, amount, self.is_creative())
+        };
+
+        self.get_inventory_mut()
+            .replace_slot_item_stack(slot, item_stack)
+    }
+
+    pub fn set_inventory_slot_amount(&mut self, slot: usize, amount: u8) -> Option<()> {
+        let item_type = self.get_inventory().get_ref_to_slot(slot)?.get_item_type();
+        self.set_inventory_slot(slot, item_type, amount)
+    }
+
+    /// Move the stack of a slot to another one, merging the stacks if they hold the same item.
+    pub fn move_inventory_item(&mut self, start_slot: usize, end_slot: usize) {
+        let slots_count = self.get_inventory().get_all_slots().len();
+        if start_slot < slots_count && end_slot < slots_count {
+            self.get_inventory_mut()
+                .move_item(start_slot, end_slot, None);
+        }
+    }
+
+    /// Merge the stacks of the same item so they use as few slots as possible. Creative slots are left as they are:
+    /// `Inventory::add_item_stack` sees them as full stacks and would replace them with finite ones.
+    pub fn merge_inventory_stacks(&mut self) {
+        let slots = self.get_inventory().get_all_slots().clone();
+
+        let mut merged: Vec<ItemStack> = Vec::new();
+        for item_stack in slots.iter().filter(|s| !s.creative_slot) {
+            let item_type = item_stack.get_item_type();
+            if item_type == ItemType::Air {
+                continue;
+            }
+
+            let max_stack = item_type.get_max_stack_amount();
+            let mut amount = item_stack.get_amount();
+            for stack in merged.iter_mut().filter(|s| s.get_item_type() == item_type) {
+                let moved = amount.min(max_stack.saturating_sub(stack.get_amount()));
+                *stack = ItemStack::new(item_type, stack.get_amount() + moved, false);
+                amount -= moved;
+            }
+            if amount > 0 {
+                merged.push(ItemStack::new(item_type, amount, false));
+            }
+        }
+
+        // The merged stacks take the finite slots in order
+        let mut merged = merged.into_iter();
+        let inventory = self.get_inventory_mut();
+        for (slot, item_stack) in slots.iter().enumerate() {
+            if !item_stack.creative_slot {
+                inventory.replace_slot_item_stack(slot, merged.next().unwrap_or(ItemStack::void()));
+            }
+        }
+    }
+
+    /// Merge the stacks then order them by item, leaving the empty slots at the end.
+    pub fn sort_inventory(&mut self) {
+        self.merge_inventory_stacks();
+
+        let mut slots = self.get_inventory().get_all_slots().clone();
+        slots.sort_by_key(|s| {
+            let item_type = s.get_item_type();
+            (
+                item_type == ItemType::Air,
+                item_type as u8,
+                u8::MAX - s.get_amount(),
+            )
+        });
+
+        let inventory = self.get_inventory_mut();
+        for (slot, item_stack) in slots.into_iter().enumerate() {
+            inventory.replace_slot_item_stack(slot, item_stack);
+        }
+    }
+
+    /// Add items to the inventory, filling the incomplete stacks first.
+    /// In Creative mode, a single infinite slot is added if the item is missing.
+    /// Returns the number of items that didn't fit.
+    pub fn give_item(&mut self, item_type: ItemType, amount: u32) -> u32 {
+        let max_stack = item_type.get_max_stack_amount() as u32;
+        if max_stack == 0 || amount == 0 {
+            return amount;
+        }
+
+        if self.is_creative() {
+            let slots = self.get_inventory().get_all_slots();
+            if slots.iter().any(|s| s.get_item_type() == item_type) {
+                return 0;
+            }
+            return match slots
+                .iter()
+                .position(|s| s.get_item_type() == ItemType::Air)
+            {
+                Some(slot) => {
+                    self.set_inventory_slot(slot, item_type, max_stack as u8);
+                    0
+                }
+                None => amount,
+            };
+        }
+
+        let mut remaining = amount;
+        while remaining > 0 {
+            let given = remaining.min(max_stack);
+            let left = self.get_inventory_mut().add_item_stack(ItemStack::new(
+                item_type,
+                given as u8,
+                false,
+            )) as u32;
+            remaining -= given - left;
+
+            if left > 0 {
+                break;
+            }
+        }
+
+        remaining
+    }
+}
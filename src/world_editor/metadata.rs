@@ -0,0 +1,99 @@
+use numcraft_format::limits::MAX_WORLD_NAME_SIZE;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::{inventory::ItemStack, save_manager::GameMode},
+    world_editor::{inventory::get_item_types, validate::is_displayable_character, World},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WorldNameError {
+    Empty,
+    TooLong { size: usize },
+    Unprintable { character: char },
+}
+
+impl WorldNameError {
+    pub fn get_description(&self) -> String {
+        match self {
+            WorldNameError::Empty => "The world name can't be empty.".to_string(),
+            WorldNameError::TooLong { size } => format!(
+                "The world name is {size} bytes long, the maximum is {MAX_WORLD_NAME_SIZE} bytes."
+            ),
+            WorldNameError::Unprintable { character } => {
+                format!("The calculator can't display {character:?}.")
+            }
+        }
+    }
+}
+
+/// Check that the name can be saved and that the calculator can display its characters.
+pub fn check_world_name(name: &str) -> Result<(), WorldNameError> {
+    if name.trim().is_empty() {
+        return Err(WorldNameError::Empty);
+    }
+
+    if name.len() > MAX_WORLD_NAME_SIZE {
+        return Err(WorldNameError::TooLong { size: name.len() });
+    }
+
+    match name.chars().find(|c| !is_displayable_character(*c)) {
+        Some(character) => Err(WorldNameError::Unprintable { character }),
+        None => Ok(()),
+    }
+}
+
+impl World {
+    pub fn rename(&mut self, name: &str) -> Result<(), WorldNameError> {
+        check_world_name(name)?;
+        self.save_manager.set_world_name(name);
+
+        Ok(())
+    }
+
+    pub fn set_seed(&mut self, seed: i32) {
+        self.save_manager.set_world_seed(seed);
+    }
+
+    /// Change the gamemode of the world. When `convert_inventory` is set, the creative slots become full finite
+    /// stacks in Survival, and the inventory is replaced by every item in Creative.
+    pub fn set_game_mode(&mut self, game_mode: GameMode, convert_inventory: bool) {
+        self.save_manager.set_gamemode(game_mode);
+
+        if !convert_inventory {
+            return;
+        }
+
+        let inventory = &mut self.save_manager.player_data.inventory;
+        let slots_count = inventory.get_all_slots().len();
+
+        match game_mode {
+            GameMode::Survival => {
+                for slot in 0..slots_count {
+                    let item_stack = *inventory.get_ref_to_slot(slot).unwrap();
+                    if item_stack.creative_slot {
+                        inventory.replace_slot_item_stack(
+                            slot,
+                            ItemStack::new(
+                                item_stack.get_item_type(),
+                                item_stack.get_amount(),
+                                false,
+                            ),
+                        );
+                    }
+                }
+            }
+            GameMode::Creative => {
+                let mut palette = get_item_types();
+                for slot in 0..slots_count {
+                    let item_stack = match palette.next() {
+                        Some(item_type) => {
+                            ItemStack::new(item_type, item_type.get_max_stack_amount(), true)
+                        }
+                        None => ItemStack::void(),
+                    };
+                    inventory.replace_slot_item_stack(slot, item_stack);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,398 @@
+/* Everything needed to edit the content of a v0.1.3 world */
+
+use std::collections::HashMap;
+
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    deserializer::{get_world_info, WorldVersion},
+    world_converter::{
+        dimensions::WorldDimensions,
+        numcraft_v0_1_3::{
+            chunk::Chunk,
+            constants::{world::CHUNK_SIZE, BlockType},
+            save_manager::{ChunkReadingError, GameMode, SaveFileLoadError, SaveManager},
+        },
+    },
+};
+use border::BorderRepairReport;
+use history::{get_checksum, EditHistory};
+use survival::{SurvivalEditError, SurvivalReport};
+
+pub mod border;
+pub mod fill;
+pub mod history;
+pub mod inventory;
+pub mod metadata;
+pub mod paint;
+pub mod player;
+pub mod query;
+pub mod sculpt;
+pub mod script;
+pub mod spawn;
+pub mod statistics;
+pub mod survival;
+pub mod validate;
+
+pub const CHUNK_SIZE_I: isize = CHUNK_SIZE as isize;
+
+/// Offsets to the six blocks sharing a face with a block.
+pub const NEIGHBOURS: [Vector3<isize>; 6] = [
+    Vector3::new(1, 0, 0),
+    Vector3::new(-1, 0, 0),
+    Vector3::new(0, 1, 0),
+    Vector3::new(0, -1, 0),
+    Vector3::new(0, 0, 1),
+    Vector3::new(0, 0, -1),
+];
+
+/// An inclusive box of blocks in world coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockBox {
+    pub min: Vector3<isize>,
+    pub max: Vector3<isize>,
+}
+
+impl BlockBox {
+    /// Build a box from any two opposite corners.
+    pub fn new(a: Vector3<isize>, b: Vector3<isize>) -> Self {
+        BlockBox {
+            min: a.inf(&b),
+            max: a.sup(&b),
+        }
+    }
+
+    /// Returns the part of the box inside the other one, or None if they don't overlap.
+    pub fn intersection(&self, other: &BlockBox) -> Option<Self> {
+        let min = self.min.sup(&other.min);
+        let max = self.max.inf(&other.max);
+
+        if min.x > max.x || min.y > max.y || min.z > max.z {
+            None
+        } else {
+            Some(BlockBox { min, max })
+        }
+    }
+
+    pub fn contains(&self, pos: Vector3<isize>) -> bool {
+        pos.x >= self.min.x
+            && pos.y >= self.min.y
+            && pos.z >= self.min.z
+            && pos.x <= self.max.x
+            && pos.y <= self.max.y
+            && pos.z <= self.max.z
+    }
+
+    /// Iterate over every block position of the box.
+    pub fn iter(&self) -> impl Iterator<Item = Vector3<isize>> {
+        let (min, max) = (self.min, self.max);
+        (min.z..=max.z).flat_map(move |z| {
+            (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Vector3::new(x, y, z)))
+        })
+    }
+}
+
+/// A small deterministic random generator so edits can be reproduced with the same seed.
+pub struct Random {
+    state: u32,
+}
+
+impl Random {
+    pub fn new(seed: u32) -> Self {
+        Random {
+            state: if seed == 0 { 0x9E37_79B9 } else { seed },
+        }
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        // Xorshift32
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        self.state
+    }
+
+    /// Returns a float in [0; 1[
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1 << 24) as f32
+    }
+}
+
+/// A block modified by `World::set_block`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BlockChange {
+    pub pos: Vector3<isize>,
+    pub old_block: BlockType,
+    pub new_block: BlockType,
+}
+
+/// Merge the changes made to the same block, and drop the blocks that are back to their first value.
+pub fn compact_changes(changes: &[BlockChange]) -> Vec<BlockChange> {
+    let mut indexes: HashMap<Vector3<isize>, usize> = HashMap::new();
+    let mut compacted: Vec<BlockChange> = Vec::new();
+
+    for change in changes {
+        match indexes.get(&change.pos) {
+            Some(index) => compacted[*index].new_block = change.new_block,
+            None => {
+                indexes.insert(change.pos, compacted.len());
+                compacted.push(*change);
+            }
+        }
+    }
+
+    compacted.retain(|change| change.old_block != change.new_block);
+    compacted
+}
+
+/// What happened to the world after an edit made through `World::edit`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EditReport {
+    pub border: BorderRepairReport,
+    /// Set when the edit was paid with the inventory of the player
+    pub survival: Option<SurvivalReport>,
+}
+
+/// A v0.1.3 world with all of its chunks decompressed so blocks can be read and edited directly.
+pub struct World {
+    pub save_manager: SaveManager,
+    chunks: Vec<Chunk>,
+    modified_chunks: Vec<bool>,
+    recorded_changes: Option<Vec<BlockChange>>,
+    history: EditHistory,
+    /// Rebuild the border after every edit made through `World::edit`
+    pub enforce_border: bool,
+    /// Make the edits made through `World::edit` cost items in Survival worlds
+    pub survival_fair: bool,
+}
+
+impl World {
+    /// Load a world with the layout of the game. Other layouts are not edited since the game can't read them back.
+    pub fn load(raw: &[u8]) -> Result<Self, WorldLoadError> {
+        if let Some(WorldVersion::Newer(world_version)) =
+            get_world_info(raw).map(|info| info.world_version)
+        {
+            return Err(WorldLoadError::NewerVersion(world_version));
+        }
+
+        let mut save_manager = SaveManager::with_dimensions(WorldDimensions::V0_1_X);
+        save_manager
+            .load_from_file(raw.to_vec())
+            .map_err(WorldLoadError::SaveFile)?;
+
+        let mut world = World::from_save_manager(save_manager).map_err(WorldLoadError::Chunk)?;
+        world.reset_history(get_checksum(raw));
+
+        Ok(world)
+    }
+
+    pub fn from_save_manager(save_manager: SaveManager) -> Result<Self, ChunkReadingError> {
+        let chunks = save_manager
+            .get_dimensions()
+            .iter_chunks()
+            .map(|pos| save_manager.get_chunk_at_pos(pos))
+            .collect::<Result<Vec<Chunk>, ChunkReadingError>>()?;
+
+        Ok(World {
+            save_manager,
+            modified_chunks: vec![false; chunks.len()],
+            chunks,
+            recorded_changes: None,
+            history: EditHistory::default(),
+            enforce_border: false,
+            survival_fair: false,
+        })
+    }
+
+    /// Create a world filled with air. The world info and the player data are the ones of the save manager.
+    #[cfg(test)]
+    pub fn new_empty(save_manager: SaveManager) -> Self {
+        let chunks: Vec<Chunk> = save_manager
+            .get_dimensions()
+            .iter_chunks()
+            .map(Chunk::new)
+            .collect();
+
+        World {
+            save_manager,
+            modified_chunks: vec![true; chunks.len()],
+            chunks,
+            recorded_changes: None,
+            history: EditHistory::default(),
+            enforce_border: false,
+            survival_fair: false,
+        }
+    }
+
+    pub fn get_dimensions(&self) -> WorldDimensions {
+        self.save_manager.get_dimensions()
+    }
+
+    /// The size of the world, in blocks.
+    pub fn get_size(&self) -> Vector3<isize> {
+        let dimensions = self.get_dimensions();
+        Vector3::new(dimensions.x, dimensions.y, dimensions.z).map(|c| c as isize * CHUNK_SIZE_I)
+    }
+
+    /// The box containing every block of the world.
+    pub fn get_bounds(&self) -> BlockBox {
+        BlockBox {
+            min: Vector3::zeros(),
+            max: self.get_size().add_scalar(-1),
+        }
+    }
+
+    pub fn is_in_world(&self, pos: Vector3<isize>) -> bool {
+        self.get_bounds().contains(pos)
+    }
+
+    pub fn get_block(&self, pos: Vector3<isize>) -> Option<BlockType> {
+        if !self.is_in_world(pos) {
+            return None;
+        }
+
+        let index = self.get_dimensions().get_chunk_index(pos / CHUNK_SIZE_I)?;
+        self.chunks[index].get_at(pos.map(|c| c % CHUNK_SIZE_I))
+    }
+
+    /// Returns true if the block is air. The outside of the world is closed by the border, so it is never air.
+    pub fn is_air(&self, pos: Vector3<isize>) -> bool {
+        self.get_block(pos).is_some_and(|b| b.is_air())
+    }
+
+    /// Set the block at the given world position. Returns true if the block changed.
+    pub fn set_block(&mut self, pos: Vector3<isize>, block_type: BlockType) -> bool {
+        let old_block = match self.get_block(pos) {
+            Some(old_block) if old_block != block_type => old_block,
+            _ => return false,
+        };
+
+        let index = self
+            .get_dimensions()
+            .get_chunk_index(pos / CHUNK_SIZE_I)
+            .unwrap();
+
+        if let Some(changes) = &mut self.recorded_changes {
+            changes.push(BlockChange {
+                pos,
+                old_block,
+                new_block: block_type,
+            });
+        }
+
+        self.chunks[index].set_at(pos.map(|c| (c % CHUNK_SIZE_I) as usize), block_type);
+        self.modified_chunks[index] = true;
+
+        true
+    }
+
+    /// Keep track of every block changed from now on.
+    pub fn start_recording_changes(&mut self) {
+        self.recorded_changes = Some(Vec::new());
+    }
+
+    /// The changes since `start_recording_changes`, merged so each block appears once.
+    pub fn get_recorded_changes(&self) -> Vec<BlockChange> {
+        self.recorded_changes
+            .as_deref()
+            .map(compact_changes)
+            .unwrap_or_default()
+    }
+
+    /// Stop recording and return the changes since `start_recording_changes`.
+    pub fn take_recorded_changes(&mut self) -> Vec<BlockChange> {
+        let changes = self.get_recorded_changes();
+        self.recorded_changes = None;
+        changes
+    }
+
+    /// Put back the blocks as they were before the changes.
+    pub fn revert_changes(&mut self, changes: &[BlockChange]) {
+        for change in changes.iter().rev() {
+            self.set_block(change.pos, change.old_block);
+        }
+    }
+
+    /// Run an edit on the world and add it to the history. In survival-fair mode, the edit is paid with the
+    /// inventory and refused if the player can't afford it. When `enforce_border` is set, the border is rebuilt
+    /// afterwards for free.
+    pub fn edit<R>(
+        &mut self,
+        description: &str,
+        edit: impl FnOnce(&mut World) -> R,
+    ) -> Result<(R, EditReport), SurvivalEditError> {
+        self.record_command(description, |world| {
+            let result = edit(world);
+            let mut report = EditReport::default();
+
+            if world.survival_fair && world.save_manager.get_game_mode() == GameMode::Survival {
+                let changes = world.get_recorded_changes();
+                match world.pay_for_changes(&changes) {
+                    Ok(survival_report) => report.survival = Some(survival_report),
+                    Err(error) => {
+                        world.revert_changes(&changes);
+                        return Err(error);
+                    }
+                }
+            }
+
+            if world.enforce_border {
+                report.border = world.repair_border();
+            }
+
+            Ok((result, report))
+        })
+    }
+
+    /// Write the modified chunks back to the save manager.
+    pub fn flush(&mut self) {
+        for (chunk, modified) in self.chunks.iter().zip(self.modified_chunks.iter_mut()) {
+            if *modified {
+                self.save_manager.set_chunk(chunk);
+                *modified = false;
+            }
+        }
+    }
+
+    pub fn get_raw(&mut self) -> Vec<u8> {
+        self.flush();
+        self.save_manager.get_raw()
+    }
+}
+
+#[derive(Debug)]
+pub enum WorldLoadError {
+    SaveFile(SaveFileLoadError),
+    Chunk(ChunkReadingError),
+    /// The world was saved by a newer version of the game, rewriting it could lose data
+    NewerVersion(u16),
+}
+
+impl WorldLoadError {
+    pub fn get_description(&self) -> String {
+        match self {
+            WorldLoadError::SaveFile(SaveFileLoadError::LimitExceeded(error)) => {
+                error.get_description()
+            }
+            WorldLoadError::SaveFile(SaveFileLoadError::FileNotFound) => {
+                "The save file is missing.".to_string()
+            }
+            WorldLoadError::SaveFile(SaveFileLoadError::CorruptedWorld) => {
+                "The save file is corrupted.".to_string()
+            }
+            WorldLoadError::Chunk(ChunkReadingError::OOBChunk) => {
+                "A chunk is outside of the world.".to_string()
+            }
+            WorldLoadError::Chunk(ChunkReadingError::CorruptedChunk) => {
+                "A chunk is corrupted.".to_string()
+            }
+            WorldLoadError::NewerVersion(version) => format!(
+                "The world was saved by a newer version of the game (world version {version})."
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,86 @@
+use nalgebra::Vector3;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::constants::BlockType,
+    world_editor::{BlockBox, World},
+};
+
+/// The blocks of a straight line between two blocks of the same layer, ends included.
+pub fn get_line(from: Vector3<isize>, to: Vector3<isize>) -> Vec<Vector3<isize>> {
+    // Bresenham's line algorithm on the X and Z axis
+    let (dx, dz) = ((to.x - from.x).abs(), -(to.z - from.z).abs());
+    let (step_x, step_z) = ((to.x - from.x).signum(), (to.z - from.z).signum());
+    let mut error = dx + dz;
+    let mut pos = from;
+    let mut line = vec![pos];
+
+    while pos.x != to.x || pos.z != to.z {
+        let double_error = 2 * error;
+        if double_error >= dz {
+            error += dz;
+            pos.x += step_x;
+        }
+        if double_error <= dx {
+            error += dx;
+            pos.z += step_z;
+        }
+        line.push(pos);
+    }
+
+    line
+}
+
+impl World {
+    /// Draw a line of blocks between two blocks of the same layer. Returns the number of blocks changed.
+    pub fn paint_line(
+        &mut self,
+        from: Vector3<isize>,
+        to: Vector3<isize>,
+        block_type: BlockType,
+    ) -> usize {
+        get_line(from, Vector3::new(to.x, from.y, to.z))
+            .into_iter()
+            .filter(|pos| self.set_block(*pos, block_type))
+            .count()
+    }
+
+    /// Fill the rectangle between two corners of the same layer. Returns the number of blocks changed.
+    pub fn paint_rectangle(
+        &mut self,
+        from: Vector3<isize>,
+        to: Vector3<isize>,
+        block_type: BlockType,
+    ) -> usize {
+        self.fill(
+            &BlockBox::new(from, Vector3::new(to.x, from.y, to.z)),
+            block_type,
+        )
+    }
+
+    /// Replace the area of identical blocks touching `start` in its layer. Returns the number of blocks changed.
+    pub fn flood_fill_layer(&mut self, start: Vector3<isize>, block_type: BlockType) -> usize {
+        let Some(target) = self.get_block(start) else {
+            return 0;
+        };
+        if target == block_type {
+            return 0;
+        }
+
+        let mut changed = 0;
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            if self.get_block(pos) != Some(target) {
+                continue;
+            }
+
+            self.set_block(pos, block_type);
+            changed += 1;
+
+            for offset in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                stack.push(pos + Vector3::new(offset.0, 0, offset.1));
+            }
+        }
+
+        changed
+    }
+}
@@ -0,0 +1,144 @@
+use core::f32::consts::{FRAC_PI_2, TAU};
+
+use nalgebra::Vector3;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::constants::BlockType,
+    world_editor::{validate::get_player_block_pos, World},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TeleportError {
+    NotFinite,
+    OutsideWorld,
+    InsideBlock {
+        block_pos: Vector3<isize>,
+        block_type: BlockType,
+    },
+}
+
+impl TeleportError {
+    pub fn get_description(&self) -> String {
+        match self {
+            TeleportError::NotFinite => "The position is not a number.".to_string(),
+            TeleportError::OutsideWorld => "The position is outside of the world.".to_string(),
+            TeleportError::InsideBlock {
+                block_pos,
+                block_type,
+            } => format!(
+                "The player would be stuck in a {block_type:?} block at x: {}, y: {}, z: {}.",
+                block_pos.x, block_pos.y, block_pos.z
+            ),
+        }
+    }
+}
+
+/// A named place the player can be teleported to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Landmark {
+    pub name: String,
+    pub feet_pos: Vector3<isize>,
+}
+
+impl World {
+    /// Check that the player can be put at this position: inside the world and not in a block.
+    /// The head may be above the top of the world as the sky is open.
+    pub fn check_player_position(&self, pos: Vector3<f32>) -> Result<(), TeleportError> {
+        if !pos.iter().all(|c| c.is_finite()) {
+            return Err(TeleportError::NotFinite);
+        }
+
+        let feet_pos = get_player_block_pos(pos);
+        if pos.iter().any(|c| *c < 0.) || !self.is_in_world(feet_pos) {
+            return Err(TeleportError::OutsideWorld);
+        }
+
+        for block_pos in [feet_pos, feet_pos + Vector3::new(0, 1, 0)] {
+            if let Some(block_type) = self.get_block(block_pos).filter(|b| !b.is_air()) {
+                return Err(TeleportError::InsideBlock {
+                    block_pos,
+                    block_type,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move the player. The position is rejected if the player would be stuck or outside of the world.
+    pub fn teleport_player(&mut self, pos: Vector3<f32>) -> Result<(), TeleportError> {
+        self.check_player_position(pos)?;
+        self.save_manager.player_data.pos = (pos.x, pos.y, pos.z);
+
+        Ok(())
+    }
+
+    /// Set the view direction of the player, in radians. The pitch is clamped to look straight up or down at most.
+    pub fn set_player_rotation(&mut self, pitch: f32, yaw: f32) {
+        let pitch = if pitch.is_finite() {
+            pitch.clamp(-FRAC_PI_2, FRAC_PI_2)
+        } else {
+            0.
+        };
+        let yaw = if yaw.is_finite() {
+            yaw.rem_euclid(TAU)
+        } else {
+            0.
+        };
+
+        self.save_manager.player_data.rotation = (pitch, yaw);
+    }
+
+    /// The feet position on top of the highest block of the column.
+    pub fn get_surface_position(&self, x: isize, z: isize) -> Option<Vector3<isize>> {
+        let feet_pos = self.get_highest_block(x, z)? + Vector3::new(0, 1, 0);
+        self.is_in_world(feet_pos).then_some(feet_pos)
+    }
+
+    /// The feet position where the player lands after falling from `pos`, or None if there is no ground below.
+    pub fn get_landing_position(&self, pos: Vector3<f32>) -> Option<Vector3<isize>> {
+        let mut feet_pos = get_player_block_pos(pos);
+
+        while feet_pos.y > 0 {
+            let below = feet_pos - Vector3::new(0, 1, 0);
+            if self.get_block(below).is_some_and(|b| !b.is_air()) {
+                return Some(feet_pos);
+            }
+            feet_pos = below;
+        }
+
+        None
+    }
+
+    pub fn get_landmarks(&self) -> Vec<Landmark> {
+        let mut landmarks = Vec::new();
+        let size = self.get_size();
+
+        if let Some(feet_pos) = self.get_surface_position(size.x / 2, size.z / 2) {
+            landmarks.push(Landmark {
+                name: "World center".to_string(),
+                feet_pos,
+            });
+        }
+
+        let highest = self
+            .iter_columns()
+            .filter_map(|(x, z)| self.get_surface_position(x, z))
+            .max_by_key(|pos| pos.y);
+        if let Some(feet_pos) = highest {
+            landmarks.push(Landmark {
+                name: "Highest point".to_string(),
+                feet_pos,
+            });
+        }
+
+        if let Some(feet_pos) = self.find_safe_spawn(self.save_manager.get_player_pos()) {
+            landmarks.push(Landmark {
+                name: "Closest safe spawn".to_string(),
+                feet_pos,
+            });
+        }
+
+        landmarks
+    }
+}
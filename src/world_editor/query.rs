@@ -0,0 +1,93 @@
+use nalgebra::Vector3;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::constants::BlockType,
+    world_editor::{BlockBox, World, CHUNK_SIZE_I, NEIGHBOURS},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockQuery {
+    /// Every block of the given type
+    BlockType(BlockType),
+    /// The highest non-air block of each column
+    HighestBlocks,
+    /// Non-air blocks with air on every face
+    FloatingBlocks,
+    /// The origin of every chunk that only contains air
+    EmptyChunks,
+}
+
+impl World {
+    /// Returns the position of every block of the box matching the predicate.
+    pub fn find_blocks(
+        &self,
+        area: &BlockBox,
+        predicate: impl Fn(Vector3<isize>, BlockType) -> bool,
+    ) -> Vec<Vector3<isize>> {
+        let Some(area) = area.intersection(&self.get_bounds()) else {
+            return Vec::new();
+        };
+
+        area.iter()
+            .filter(|pos| predicate(*pos, self.get_block(*pos).unwrap()))
+            .collect()
+    }
+
+    /// Iterate over the (x, z) position of every column, ordered by z then x.
+    pub fn iter_columns(&self) -> impl Iterator<Item = (isize, isize)> {
+        let size = self.get_size();
+        (0..size.z).flat_map(move |z| (0..size.x).map(move |x| (x, z)))
+    }
+
+    /// Returns the highest non-air block of the column, or None if the column is empty.
+    pub fn get_highest_block(&self, x: isize, z: isize) -> Option<Vector3<isize>> {
+        (0..self.get_size().y)
+            .rev()
+            .map(|y| Vector3::new(x, y, z))
+            .find(|pos| self.get_block(*pos).is_some_and(|b| !b.is_air()))
+    }
+
+    /// Returns the highest non-air block of every column, ordered by z then x.
+    pub fn get_height_map(&self) -> Vec<Option<isize>> {
+        self.iter_columns()
+            .map(|(x, z)| self.get_highest_block(x, z).map(|pos| pos.y))
+            .collect()
+    }
+
+    /// Returns true if the block isn't air and all its neighbours are. A block against the outside of the world
+    /// leans on it, like against the border.
+    pub fn is_floating(&self, pos: Vector3<isize>) -> bool {
+        self.get_block(pos).is_some_and(|b| !b.is_air())
+            && NEIGHBOURS.iter().all(|offset| self.is_air(pos + offset))
+    }
+
+    /// Returns the position of every chunk only made of air.
+    pub fn get_empty_chunks(&self) -> Vec<Vector3<isize>> {
+        self.get_dimensions()
+            .iter_chunks()
+            .filter(|chunk_pos| {
+                let origin = chunk_pos * CHUNK_SIZE_I;
+                BlockBox::new(origin, origin.add_scalar(CHUNK_SIZE_I - 1))
+                    .iter()
+                    .all(|pos| self.get_block(pos).is_some_and(|b| b.is_air()))
+            })
+            .collect()
+    }
+
+    /// Run a query over the whole world. Chunk positions are returned for `EmptyChunks`.
+    pub fn run_query(&self, query: BlockQuery) -> Vec<Vector3<isize>> {
+        match query {
+            BlockQuery::BlockType(block_type) => {
+                self.find_blocks(&self.get_bounds(), |_, b| b == block_type)
+            }
+            BlockQuery::HighestBlocks => self
+                .iter_columns()
+                .filter_map(|(x, z)| self.get_highest_block(x, z))
+                .collect(),
+            BlockQuery::FloatingBlocks => {
+                self.find_blocks(&self.get_bounds(), |pos, _| self.is_floating(pos))
+            }
+            BlockQuery::EmptyChunks => self.get_empty_chunks(),
+        }
+    }
+}
@@ -0,0 +1,614 @@
+use nalgebra::Vector3;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::{
+        constants::{BlockType, ItemType},
+        save_manager::GameMode,
+    },
+    world_editor::{inventory::get_item_types, BlockBox, World},
+};
+
+/// The scripts shipped with the site, as (name, source).
+pub const EXAMPLE_SCRIPTS: [(&str, &str); 3] = [
+    (
+        "Reset a student world",
+        "replace Sand with Stone below y=10, give 64 Planks, reset gamemode convert inventory",
+    ),
+    (
+        "Creative sandbox",
+        "# Every block, in infinite amount\ngamemode creative convert inventory\nrepair border\nteleport safe",
+    ),
+    (
+        "Clean inventory",
+        "clear inventory\ngive 64 Planks\ngive 32 Cobblestone",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptError {
+    UnknownCommand {
+        line: usize,
+        command: String,
+    },
+    MissingArgument {
+        line: usize,
+        expected: &'static str,
+    },
+    InvalidArgument {
+        line: usize,
+        argument: String,
+        expected: &'static str,
+    },
+    UnclosedQuote {
+        line: usize,
+    },
+}
+
+impl ScriptError {
+    pub fn get_description(&self) -> String {
+        match self {
+            ScriptError::UnknownCommand { line, command } => {
+                format!("Line {line}: unknown command \"{command}\".")
+            }
+            ScriptError::MissingArgument { line, expected } => {
+                format!("Line {line}: {expected} is missing.")
+            }
+            ScriptError::InvalidArgument {
+                line,
+                argument,
+                expected,
+            } => format!("Line {line}: expected {expected}, found \"{argument}\"."),
+            ScriptError::UnclosedQuote { line } => format!("Line {line}: a quote is not closed."),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum ScriptCommand {
+    /// Replace the blocks whose height is between `min_y` and `max_y`, included.
+    Replace {
+        from: BlockType,
+        to: BlockType,
+        min_y: Option<isize>,
+        max_y: Option<isize>,
+    },
+    Fill {
+        area: BlockBox,
+        block_type: BlockType,
+    },
+    /// Empty the inside of the box and build its six sides
+    Hollow {
+        area: BlockBox,
+        block_type: BlockType,
+    },
+    /// Build the four vertical sides of the box
+    Walls {
+        area: BlockBox,
+        block_type: BlockType,
+    },
+    Give {
+        item_type: ItemType,
+        amount: u32,
+    },
+    ClearInventory,
+    /// None resets the gamemode of a new world.
+    SetGameMode {
+        game_mode: Option<GameMode>,
+        convert_inventory: bool,
+    },
+    Rename {
+        name: String,
+    },
+    SetSeed {
+        seed: i32,
+    },
+    Teleport {
+        pos: Vector3<f32>,
+    },
+    MoveToSafeSpawn,
+    RepairBorder,
+}
+
+impl ScriptCommand {
+    pub fn get_description(&self) -> String {
+        match self {
+            ScriptCommand::Replace {
+                from,
+                to,
+                min_y,
+                max_y,
+            } => {
+                let mut description = format!("Replace {from:?} with {to:?}");
+                if let Some(max_y) = max_y {
+                    description += &format!(" below y={}", max_y + 1);
+                }
+                if let Some(min_y) = min_y {
+                    description += &format!(" above y={}", min_y - 1);
+                }
+                description
+            }
+            ScriptCommand::Fill { area, block_type } => {
+                format!("Fill {} with {block_type:?}", get_area_description(area))
+            }
+            ScriptCommand::Hollow { area, block_type } => {
+                format!("Hollow {} with {block_type:?}", get_area_description(area))
+            }
+            ScriptCommand::Walls { area, block_type } => format!(
+                "Build walls {} with {block_type:?}",
+                get_area_description(area)
+            ),
+            ScriptCommand::Give { item_type, amount } => {
+                format!("Give {amount} {}", get_item_name(*item_type))
+            }
+            ScriptCommand::ClearInventory => "Clear the inventory".to_string(),
+            ScriptCommand::SetGameMode {
+                game_mode,
+                convert_inventory,
+            } => {
+                let description = match game_mode {
+                    Some(GameMode::Survival) => "Set the gamemode to Survival",
+                    Some(GameMode::Creative) => "Set the gamemode to Creative",
+                    None => "Reset the gamemode",
+                };
+                if *convert_inventory {
+                    format!("{description} and convert the inventory")
+                } else {
+                    description.to_string()
+                }
+            }
+            ScriptCommand::Rename { name } => format!("Rename the world to \"{name}\""),
+            ScriptCommand::SetSeed { seed } => format!("Set the seed to {seed}"),
+            ScriptCommand::Teleport { pos } => {
+                format!("Teleport to {} {} {}", pos.x, pos.y, pos.z)
+            }
+            ScriptCommand::MoveToSafeSpawn => "Move to a safe spawn".to_string(),
+            ScriptCommand::RepairBorder => "Repair the border".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct ScriptStatement {
+    pub line: usize,
+    pub command: ScriptCommand,
+}
+
+/// What a statement did to the world, or why it failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptEffect {
+    pub line: usize,
+    pub description: String,
+    pub result: String,
+    pub failed: bool,
+}
+
+fn get_area_description(area: &BlockBox) -> String {
+    format!(
+        "from {} {} {} to {} {} {}",
+        area.min.x, area.min.y, area.min.z, area.max.x, area.max.y, area.max.z
+    )
+}
+
+fn get_item_name(item_type: ItemType) -> String {
+    match item_type.get_matching_block_type() {
+        Some(block_type) => format!("{block_type:?}"),
+        None => "Air".to_string(),
+    }
+}
+
+/// Blocks are named like in the search panel, case insensitive.
+fn parse_block_type(line: usize, word: &str) -> Result<BlockType, ScriptError> {
+    (0..=u8::MAX)
+        .filter_map(BlockType::get_from_id)
+        .find(|block_type| format!("{block_type:?}").eq_ignore_ascii_case(word))
+        .ok_or(ScriptError::InvalidArgument {
+            line,
+            argument: word.to_string(),
+            expected: "a block name",
+        })
+}
+
+/// Items are named after the block they place. The "Block" suffix is optional.
+fn parse_item_type(line: usize, word: &str) -> Result<ItemType, ScriptError> {
+    let name = match word.len().checked_sub(5) {
+        Some(i)
+            if word
+                .get(i..)
+                .is_some_and(|s| s.eq_ignore_ascii_case("block")) =>
+        {
+            &word[..i]
+        }
+        _ => word,
+    };
+
+    get_item_types()
+        .find(|item_type| get_item_name(*item_type).eq_ignore_ascii_case(name))
+        .ok_or(ScriptError::InvalidArgument {
+            line,
+            argument: word.to_string(),
+            expected: "an item name",
+        })
+}
+
+fn parse_number<T: std::str::FromStr>(
+    line: usize,
+    word: &str,
+    expected: &'static str,
+) -> Result<T, ScriptError> {
+    word.parse().map_err(|_| ScriptError::InvalidArgument {
+        line,
+        argument: word.to_string(),
+        expected,
+    })
+}
+
+/// The words of a statement, consumed from left to right.
+struct Words<'a> {
+    line: usize,
+    words: std::slice::Iter<'a, String>,
+}
+
+impl<'a> Words<'a> {
+    fn next(&mut self, expected: &'static str) -> Result<&'a str, ScriptError> {
+        self.words
+            .next()
+            .map(|word| word.as_str())
+            .ok_or(ScriptError::MissingArgument {
+                line: self.line,
+                expected,
+            })
+    }
+
+    /// Consume the next word if it is the given keyword.
+    fn accept(&mut self, keyword: &str) -> bool {
+        let mut peek = self.words.clone();
+        if peek
+            .next()
+            .is_some_and(|word| word.eq_ignore_ascii_case(keyword))
+        {
+            self.words = peek;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, keyword: &'static str) -> Result<(), ScriptError> {
+        let word = self.next(keyword)?;
+        if word.eq_ignore_ascii_case(keyword) {
+            Ok(())
+        } else {
+            Err(ScriptError::InvalidArgument {
+                line: self.line,
+                argument: word.to_string(),
+                expected: keyword,
+            })
+        }
+    }
+
+    fn position<T: std::str::FromStr + nalgebra::Scalar>(
+        &mut self,
+    ) -> Result<Vector3<T>, ScriptError> {
+        let mut coordinates = Vec::new();
+        for _ in 0..3 {
+            let word = self.next("a coordinate")?;
+            coordinates.push(parse_number(self.line, word, "a coordinate")?);
+        }
+        Ok(Vector3::from_vec(coordinates))
+    }
+
+    fn end(&mut self) -> Result<(), ScriptError> {
+        match self.words.next() {
+            Some(word) => Err(ScriptError::InvalidArgument {
+                line: self.line,
+                argument: word.to_string(),
+                expected: "the end of the command",
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Split a line in statements separated by commas. Quoted words can contain spaces, commas and '#'.
+fn split_line(line: usize, text: &str) -> Result<Vec<Vec<String>>, ScriptError> {
+    let mut statements = vec![Vec::new()];
+    let mut word: Option<String> = None;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let mut quoted = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => quoted.push(c),
+                        None => return Err(ScriptError::UnclosedQuote { line }),
+                    }
+                }
+                let words = statements.last_mut().unwrap();
+                words.extend(word.take());
+                words.push(quoted);
+            }
+            '#' => break,
+            ',' => {
+                statements.last_mut().unwrap().extend(word.take());
+                statements.push(Vec::new());
+            }
+            c if c.is_whitespace() => statements.last_mut().unwrap().extend(word.take()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    statements.last_mut().unwrap().extend(word.take());
+
+    Ok(statements
+        .into_iter()
+        .filter(|words| !words.is_empty())
+        .collect())
+}
+
+fn parse_statement(line: usize, words: &[String]) -> Result<ScriptCommand, ScriptError> {
+    let mut words = Words {
+        line,
+        words: words.iter(),
+    };
+    let command = words.next("a command")?.to_lowercase();
+
+    let result = match command.as_str() {
+        "replace" => {
+            let from = parse_block_type(line, words.next("a block name")?)?;
+            words.expect("with")?;
+            let to = parse_block_type(line, words.next("a block name")?)?;
+
+            let (mut min_y, mut max_y) = (None, None);
+            loop {
+                let below = if words.accept("below") {
+                    true
+                } else if words.accept("above") {
+                    false
+                } else {
+                    break;
+                };
+                let word = words.next("a height")?;
+                let y: isize = match word.get(..2) {
+                    Some(prefix) if prefix.eq_ignore_ascii_case("y=") => {
+                        parse_number(line, &word[2..], "a height like y=10")?
+                    }
+                    _ => parse_number(line, word, "a height like y=10")?,
+                };
+                if below {
+                    max_y = Some(y - 1);
+                } else {
+                    min_y = Some(y + 1);
+                }
+            }
+
+            ScriptCommand::Replace {
+                from,
+                to,
+                min_y,
+                max_y,
+            }
+        }
+        "fill" | "hollow" | "walls" => {
+            let a = words.position()?;
+            words.expect("to")?;
+            let b = words.position()?;
+            words.expect("with")?;
+            let block_type = parse_block_type(line, words.next("a block name")?)?;
+            let area = BlockBox::new(a, b);
+            match command.as_str() {
+                "fill" => ScriptCommand::Fill { area, block_type },
+                "hollow" => ScriptCommand::Hollow { area, block_type },
+                _ => ScriptCommand::Walls { area, block_type },
+            }
+        }
+        "give" => {
+            let amount = parse_number(line, words.next("an amount")?, "an amount")?;
+            let item_type = parse_item_type(line, words.next("an item name")?)?;
+            ScriptCommand::Give { item_type, amount }
+        }
+        "clear" => {
+            words.expect("inventory")?;
+            ScriptCommand::ClearInventory
+        }
+        "gamemode" | "reset" => {
+            let game_mode = if command == "reset" {
+                words.expect("gamemode")?;
+                None
+            } else {
+                let word = words.next("survival or creative")?;
+                match word.to_lowercase().as_str() {
+                    "survival" => Some(GameMode::Survival),
+                    "creative" => Some(GameMode::Creative),
+                    _ => {
+                        return Err(ScriptError::InvalidArgument {
+                            line,
+                            argument: word.to_string(),
+                            expected: "survival or creative",
+                        })
+                    }
+                }
+            };
+            // The inventory is only rewritten when asked, as creative mode replaces it with every item
+            let convert_inventory = words.accept("convert");
+            if convert_inventory {
+                words.expect("inventory")?;
+            }
+            ScriptCommand::SetGameMode {
+                game_mode,
+                convert_inventory,
+            }
+        }
+        "rename" => ScriptCommand::Rename {
+            name: words.next("a world name")?.to_string(),
+        },
+        "seed" => ScriptCommand::SetSeed {
+            seed: parse_number(line, words.next("a seed")?, "a seed")?,
+        },
+        "teleport" => {
+            if words.accept("safe") {
+                ScriptCommand::MoveToSafeSpawn
+            } else {
+                ScriptCommand::Teleport {
+                    pos: words.position()?,
+                }
+            }
+        }
+        "repair" => {
+            words.expect("border")?;
+            ScriptCommand::RepairBorder
+        }
+        _ => return Err(ScriptError::UnknownCommand { line, command }),
+    };
+
+    words.end()?;
+    Ok(result)
+}
+
+/// Parse a script. Commands are separated by new lines or commas and '#' starts a comment.
+pub fn parse_script(source: &str) -> Result<Vec<ScriptStatement>, ScriptError> {
+    let mut statements = Vec::new();
+
+    for (i, text) in source.lines().enumerate() {
+        let line = i + 1;
+        for words in split_line(line, text)? {
+            statements.push(ScriptStatement {
+                line,
+                command: parse_statement(line, &words)?,
+            });
+        }
+    }
+
+    Ok(statements)
+}
+
+impl World {
+    /// Run a statement as a single command of the history. Returns the effect, or the reason it failed.
+    fn run_statement(&mut self, statement: &ScriptStatement) -> Result<String, String> {
+        let description = format!("Script: {}", statement.command.get_description());
+
+        match &statement.command {
+            ScriptCommand::Replace {
+                from,
+                to,
+                min_y,
+                max_y,
+            } => {
+                let bounds = self.get_bounds();
+                let min_y = min_y.unwrap_or(bounds.min.y).max(bounds.min.y);
+                let max_y = max_y.unwrap_or(bounds.max.y).min(bounds.max.y);
+                if min_y > max_y {
+                    return Ok("No block is in this range.".to_string());
+                }
+
+                let area = BlockBox {
+                    min: Vector3::new(bounds.min.x, min_y, bounds.min.z),
+                    max: Vector3::new(bounds.max.x, max_y, bounds.max.z),
+                };
+                self.edit(&description, |world| {
+                    world.replace(&area, *from, *to, &Default::default())
+                })
+                .map(|(changed, _)| format!("{changed} blocks replaced."))
+                .map_err(|error| error.get_description())
+            }
+            ScriptCommand::Fill { area, block_type } => self
+                .edit(&description, |world| world.fill(area, *block_type))
+                .map(|(changed, _)| format!("{changed} blocks changed."))
+                .map_err(|error| error.get_description()),
+            ScriptCommand::Hollow { area, block_type } => self
+                .edit(&description, |world| world.hollow(area, *block_type))
+                .map(|(changed, _)| format!("{changed} blocks changed."))
+                .map_err(|error| error.get_description()),
+            ScriptCommand::Walls { area, block_type } => self
+                .edit(&description, |world| world.walls(area, *block_type))
+                .map(|(changed, _)| format!("{changed} blocks changed."))
+                .map_err(|error| error.get_description()),
+            ScriptCommand::Give { item_type, amount } => {
+                let remaining =
+                    self.edit_state(&description, |world| world.give_item(*item_type, *amount));
+                if remaining == 0 {
+                    Ok("Everything was added to the inventory.".to_string())
+                } else {
+                    Err(format!("{remaining} items didn't fit in the inventory."))
+                }
+            }
+            ScriptCommand::ClearInventory => {
+                self.edit_state(&description, |world| {
+                    for slot in 0..world.get_inventory().get_all_slots().len() {
+                        world.set_inventory_slot(slot, ItemType::Air, 0);
+                    }
+                });
+                Ok("The inventory is empty.".to_string())
+            }
+            ScriptCommand::SetGameMode {
+                game_mode,
+                convert_inventory,
+            } => {
+                // New worlds start in Survival
+                let game_mode = game_mode.unwrap_or(GameMode::Survival);
+                self.edit_state(&description, |world| {
+                    world.set_game_mode(game_mode, *convert_inventory)
+                });
+                Ok("The gamemode was changed.".to_string())
+            }
+            ScriptCommand::Rename { name } => self
+                .edit_state(&description, |world| world.rename(name))
+                .map(|_| "The world was renamed.".to_string())
+                .map_err(|error| error.get_description()),
+            ScriptCommand::SetSeed { seed } => {
+                self.edit_state(&description, |world| world.set_seed(*seed));
+                Ok("The seed was changed.".to_string())
+            }
+            ScriptCommand::Teleport { pos } => self
+                .edit_state(&description, |world| world.teleport_player(*pos))
+                .map(|_| "The player was teleported.".to_string())
+                .map_err(|error| error.get_description()),
+            ScriptCommand::MoveToSafeSpawn => {
+                match self.edit_state(&description, |world| world.move_player_to_safe_spawn()) {
+                    Some(pos) => Ok(format!(
+                        "The player was moved to x: {:.1}, y: {:.1}, z: {:.1}.",
+                        pos.x, pos.y, pos.z
+                    )),
+                    None if self
+                        .find_safe_spawn(self.save_manager.get_player_pos())
+                        .is_none() =>
+                    {
+                        Err("There is no safe place to stand in this world.".to_string())
+                    }
+                    None => Ok("The player is already standing safely.".to_string()),
+                }
+            }
+            ScriptCommand::RepairBorder => {
+                // The border is free, even in survival-fair mode
+                let report = self.edit_state(&description, |world| world.repair_border());
+                Ok(report.get_description())
+            }
+        }
+    }
+
+    /// Run every statement of a script. A failed statement doesn't stop the script. In a dry run, the world is put
+    /// back as it was and only the effects are returned.
+    pub fn run_script(&mut self, script: &[ScriptStatement], dry_run: bool) -> Vec<ScriptEffect> {
+        let run = |world: &mut World| {
+            script
+                .iter()
+                .map(|statement| {
+                    let result = world.run_statement(statement);
+                    ScriptEffect {
+                        line: statement.line,
+                        description: statement.command.get_description(),
+                        failed: result.is_err(),
+                        result: result.unwrap_or_else(|error| error),
+                    }
+                })
+                .collect()
+        };
+
+        if dry_run {
+            self.dry_run(run)
+        } else {
+            run(self)
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use nalgebra::Vector3;
+
+use crate::{world_converter::numcraft_v0_1_3::constants::BlockType, world_editor::World};
+
+// Number of Dirt blocks between the Grass and the Stone
+const DIRT_DEPTH: isize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BrushKind {
+    Raise,
+    Lower,
+    /// Move each column towards the average height of its neighbours
+    Smooth,
+    /// Move each column towards the given ground height
+    Flatten {
+        level: isize,
+    },
+    /// Move the ground from the steep slopes to the bottom of the slopes
+    Erode,
+}
+
+impl BrushKind {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            BrushKind::Raise => "Raise",
+            BrushKind::Lower => "Lower",
+            BrushKind::Smooth => "Smooth",
+            BrushKind::Flatten { .. } => "Flatten",
+            BrushKind::Erode => "Erode",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Brush {
+    pub kind: BrushKind,
+    /// In blocks
+    pub radius: f32,
+    /// From 0 to 1
+    pub strength: f32,
+}
+
+/// The new ground height of a column touched by a brush.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColumnChange {
+    pub x: isize,
+    pub z: isize,
+    pub old_height: isize,
+    pub new_height: isize,
+}
+
+/// The natural block at `depth` blocks below the surface.
+pub fn get_layer_block(depth: isize) -> BlockType {
+    match depth {
+        0 => BlockType::Grass,
+        1..=DIRT_DEPTH => BlockType::Dirt,
+        _ => BlockType::Stone,
+    }
+}
+
+impl World {
+    /// The number of blocks from the bottom of the world to the top of the highest block of the column.
+    pub fn get_ground_height(&self, x: isize, z: isize) -> isize {
+        self.get_highest_block(x, z).map_or(0, |pos| pos.y + 1)
+    }
+
+    /// Columns of the border walls can't be sculpted.
+    fn is_sculptable_column(&self, x: isize, z: isize) -> bool {
+        let size = self.get_size();
+        x > 0 && z > 0 && x < size.x - 1 && z < size.z - 1
+    }
+
+    /// Compute the changes the brush would make around the center column, without modifying the world.
+    pub fn get_brush_preview(&self, center: (isize, isize), brush: &Brush) -> Vec<ColumnChange> {
+        let size = self.get_size();
+        let radius = brush.radius.max(0.5);
+        let strength = brush.strength.clamp(0., 1.);
+        let reach = radius.ceil() as isize;
+
+        // The floor of the world is part of the border and the player needs room above the ground
+        let (min_height, max_height) = (1, size.y - 2);
+
+        let mut columns = Vec::new();
+        for z in (center.1 - reach)..=(center.1 + reach) {
+            for x in (center.0 - reach)..=(center.0 + reach) {
+                let distance = (((x - center.0).pow(2) + (z - center.1).pow(2)) as f32).sqrt();
+                if distance <= radius && self.is_sculptable_column(x, z) {
+                    columns.push((x, z, 1. - distance / (radius + 1.)));
+                }
+            }
+        }
+
+        let heights: Vec<isize> = columns
+            .iter()
+            .map(|(x, z, _)| self.get_ground_height(*x, *z))
+            .collect();
+        let mut new_heights: Vec<f32> = heights.iter().map(|h| *h as f32).collect();
+
+        for (i, (x, z, falloff)) in columns.iter().enumerate() {
+            let weight = strength * falloff;
+            let height = heights[i] as f32;
+
+            new_heights[i] = match brush.kind {
+                BrushKind::Raise => height + weight * radius,
+                BrushKind::Lower => height - weight * radius,
+                BrushKind::Smooth => {
+                    let neighbours: Vec<f32> = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                        .iter()
+                        .filter(|(dx, dz)| self.is_sculptable_column(x + dx, z + dz))
+                        .map(|(dx, dz)| self.get_ground_height(x + dx, z + dz) as f32)
+                        .collect();
+                    if neighbours.is_empty() {
+                        height
+                    } else {
+                        let average = neighbours.iter().sum::<f32>() / neighbours.len() as f32;
+                        height + (average - height) * weight
+                    }
+                }
+                BrushKind::Flatten { level } => height + (level as f32 - height) * weight,
+                BrushKind::Erode => new_heights[i],
+            };
+
+            if brush.kind == BrushKind::Erode {
+                // Move half of the slope above one block to the lowest neighbour
+                let lowest = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                    .iter()
+                    .filter_map(|(dx, dz)| {
+                        columns
+                            .iter()
+                            .position(|(nx, nz, _)| *nx == x + dx && *nz == z + dz)
+                    })
+                    .min_by_key(|j| heights[*j]);
+
+                if let Some(j) = lowest {
+                    let slope = heights[i] - heights[j];
+                    if slope > 1 {
+                        let moved = ((slope - 1) as f32 / 2.) * weight;
+                        new_heights[i] -= moved;
+                        new_heights[j] += moved;
+                    }
+                }
+            }
+        }
+
+        columns
+            .iter()
+            .zip(heights.iter().zip(new_heights.iter()))
+            .filter_map(|((x, z, _), (old_height, new_height))| {
+                let new_height = (new_height.round() as isize).clamp(min_height, max_height);
+                let new_height = if *old_height < min_height || *old_height > max_height {
+                    *old_height
+                } else {
+                    new_height
+                };
+                (new_height != *old_height).then_some(ColumnChange {
+                    x: *x,
+                    z: *z,
+                    old_height: *old_height,
+                    new_height,
+                })
+            })
+            .collect()
+    }
+
+    /// Set the ground height of a column, keeping the Grass, Dirt and Stone layers.
+    /// Returns the number of blocks changed.
+    pub fn set_ground_height(&mut self, change: &ColumnChange) -> usize {
+        let pos = |y| Vector3::new(change.x, y, change.z);
+        let mut changed = 0;
+
+        if change.new_height > change.old_height {
+            // The old surface is buried
+            for y in (change.old_height - 1 - DIRT_DEPTH).max(0)..change.old_height {
+                let depth = change.new_height - 1 - y;
+                if self.get_block(pos(y)) == Some(BlockType::Grass) {
+                    changed += self.set_block(pos(y), get_layer_block(depth)) as usize;
+                }
+            }
+            for y in change.old_height..change.new_height {
+                changed +=
+                    self.set_block(pos(y), get_layer_block(change.new_height - 1 - y)) as usize;
+            }
+        } else {
+            for y in change.new_height..change.old_height {
+                changed += self.set_block(pos(y), BlockType::Air) as usize;
+            }
+            let top = pos(change.new_height - 1);
+            if self.get_block(top) == Some(BlockType::Dirt) {
+                changed += self.set_block(top, BlockType::Grass) as usize;
+            }
+        }
+
+        changed
+    }
+
+    /// Apply a brush around the center column. Returns the number of blocks changed.
+    pub fn apply_brush(&mut self, center: (isize, isize), brush: &Brush) -> usize {
+        self.get_brush_preview(center, brush)
+            .iter()
+            .map(|change| self.set_ground_height(change))
+            .sum()
+    }
+}
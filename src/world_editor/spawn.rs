@@ -0,0 +1,71 @@
+use nalgebra::Vector3;
+
+use crate::world_editor::{validate::get_player_block_pos, World};
+
+// Blocks of free space needed above the ground for the player to stand
+const PLAYER_HEIGHT: isize = 2;
+
+impl World {
+    /// Returns true if the player can stand with the feet in this block.
+    pub fn is_safe_spawn(&self, feet_pos: Vector3<isize>) -> bool {
+        self.get_block(feet_pos - Vector3::new(0, 1, 0))
+            .is_some_and(|b| !b.is_air())
+            && (0..PLAYER_HEIGHT).all(|dy| {
+                self.get_block(feet_pos + Vector3::new(0, dy, 0))
+                    .is_some_and(|b| b.is_air())
+            })
+    }
+
+    /// Returns the safe feet position closest to `from`, or None if there is no place to stand in the world.
+    pub fn find_safe_spawn(&self, from: Vector3<f32>) -> Option<Vector3<isize>> {
+        let from = if from.iter().all(|c| c.is_finite()) {
+            from
+        } else {
+            self.get_size().map(|c| c as f32 / 2.)
+        };
+
+        let mut best: Option<(f32, Vector3<isize>)> = None;
+
+        for (x, z) in self.iter_columns() {
+            for y in 1..self.get_size().y {
+                let feet_pos = Vector3::new(x, y, z);
+                if !self.is_safe_spawn(feet_pos) {
+                    continue;
+                }
+
+                let distance = (get_standing_position(feet_pos) - from).norm_squared();
+                if best.is_none_or(|(best_distance, _)| distance < best_distance) {
+                    best = Some((distance, feet_pos));
+                }
+            }
+        }
+
+        best.map(|(_, feet_pos)| feet_pos)
+    }
+
+    /// Move the player to the closest safe position if they are not already standing safely.
+    /// The rotation of the player is kept. Returns the new position, or None if the player didn't move.
+    pub fn move_player_to_safe_spawn(&mut self) -> Option<Vector3<f32>> {
+        let player_pos = self.save_manager.get_player_pos();
+
+        if player_pos.iter().all(|c| c.is_finite())
+            && self.is_safe_spawn(get_player_block_pos(player_pos))
+        {
+            return None;
+        }
+
+        let new_pos = get_standing_position(self.find_safe_spawn(player_pos)?);
+        self.save_manager.player_data.pos = (new_pos.x, new_pos.y, new_pos.z);
+
+        Some(new_pos)
+    }
+}
+
+/// The player position when standing in the middle of the block.
+pub fn get_standing_position(feet_pos: Vector3<isize>) -> Vector3<f32> {
+    Vector3::new(
+        feet_pos.x as f32 + 0.5,
+        feet_pos.y as f32,
+        feet_pos.z as f32 + 0.5,
+    )
+}
@@ -0,0 +1,217 @@
+/* Statistics of a world computed from its decoded chunks, its saved data and its player data, exportable as CSV or
+JSON */
+
+use serde::Serialize;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::constants::{BlockType, ItemType},
+    world_editor::{inventory::get_item_types, World},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BlockCount {
+    #[serde(skip)]
+    pub block_type: BlockType,
+    pub block: &'static str,
+    pub count: usize,
+    /// The share of the blocks of the world, in percent
+    pub percentage: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChunkSize {
+    pub pos: (isize, isize, isize),
+    /// The size of the chunk once compressed in the save file, in bytes
+    pub compressed_size: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ItemTotal {
+    pub item: &'static str,
+    /// Creative slots count as a full stack
+    pub amount: usize,
+    pub slots: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WorldStatistics {
+    pub world_name: String,
+    pub block_count: usize,
+    pub solid_block_count: usize,
+    /// The share of the world that isn't air, in percent
+    pub solid_percentage: f32,
+    /// Every block type, air included, in the order of their ids
+    pub blocks: Vec<BlockCount>,
+    /// The number of columns whose highest block is at each height
+    pub height_histogram: Vec<usize>,
+    /// The columns that only contain air
+    pub empty_columns: usize,
+    pub chunks: Vec<ChunkSize>,
+    /// The size of the world saved to a file, in bytes
+    pub file_size: usize,
+    /// How many times smaller the file is than the blocks stored with one byte each
+    pub compression_ratio: f32,
+    /// Every item of the inventory, in the order of their ids
+    pub items: Vec<ItemTotal>,
+    pub used_slots: usize,
+    pub slot_count: usize,
+}
+
+/// Quote a CSV field if it contains a separator, a quote or a line break.
+fn get_csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn get_percentage(part: usize, total: usize) -> f32 {
+    if total == 0 {
+        0.
+    } else {
+        part as f32 * 100. / total as f32
+    }
+}
+
+impl WorldStatistics {
+    /// One statistic per line, as `section,name,value`, so every world gives the same columns.
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec!["section,name,value".to_string()];
+        let mut add_line = |section: &str, name: &str, value: String| {
+            lines.push(format!(
+                "{},{},{}",
+                get_csv_field(section),
+                get_csv_field(name),
+                get_csv_field(&value)
+            ));
+        };
+
+        add_line("world", "name", self.world_name.clone());
+        add_line("world", "blocks", self.block_count.to_string());
+        add_line("world", "solid blocks", self.solid_block_count.to_string());
+        add_line(
+            "world",
+            "solid percentage",
+            format!("{:.2}", self.solid_percentage),
+        );
+        add_line("world", "empty columns", self.empty_columns.to_string());
+        add_line("world", "file size", self.file_size.to_string());
+        add_line(
+            "world",
+            "compression ratio",
+            format!("{:.2}", self.compression_ratio),
+        );
+        add_line("world", "used slots", self.used_slots.to_string());
+        add_line("world", "slots", self.slot_count.to_string());
+
+        for block in &self.blocks {
+            add_line("block", block.block, block.count.to_string());
+        }
+        for (height, columns) in self.height_histogram.iter().enumerate() {
+            add_line("height", &height.to_string(), columns.to_string());
+        }
+        for chunk in &self.chunks {
+            let (x, y, z) = chunk.pos;
+            add_line(
+                "chunk",
+                &format!("{x} {y} {z}"),
+                chunk.compressed_size.to_string(),
+            );
+        }
+        for item in &self.items {
+            add_line("item", item.item, item.amount.to_string());
+        }
+
+        let mut csv = lines.join("\n");
+        csv.push('\n');
+        csv
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("The statistics can always be serialized.")
+    }
+}
+
+impl World {
+    pub fn get_statistics(&self) -> WorldStatistics {
+        let mut counts = [0usize; u8::MAX as usize + 1];
+        for chunk in &self.chunks {
+            for block in chunk.get_all_blocks() {
+                counts[*block as usize] += 1;
+            }
+        }
+        let block_count: usize = counts.iter().sum();
+        let solid_block_count = block_count - counts[BlockType::Air as usize];
+
+        let blocks = (0..=u8::MAX)
+            .filter_map(BlockType::get_from_id)
+            .map(|block_type| BlockCount {
+                block_type,
+                block: block_type.get_name(),
+                count: counts[block_type as usize],
+                percentage: get_percentage(counts[block_type as usize], block_count),
+            })
+            .collect();
+
+        let mut height_histogram = vec![0; self.get_size().y as usize];
+        let mut empty_columns = 0;
+        for height in self.get_height_map() {
+            match height {
+                Some(y) => height_histogram[y as usize] += 1,
+                None => empty_columns += 1,
+            }
+        }
+
+        // Only the chunks that weren't flushed yet are compressed again
+        let mut save_manager = self.save_manager.clone();
+        for (chunk, modified) in self.chunks.iter().zip(&self.modified_chunks) {
+            if *modified {
+                save_manager.set_chunk(chunk);
+            }
+        }
+        let chunks = self
+            .chunks
+            .iter()
+            .map(|chunk| {
+                let pos = chunk.get_pos();
+                ChunkSize {
+                    pos: (pos.x, pos.y, pos.z),
+                    compressed_size: save_manager.get_chunk_data_size(*pos).unwrap_or(0),
+                }
+            })
+            .collect();
+        let file_size = save_manager.get_file_size();
+
+        let slots = self.get_inventory().get_all_slots();
+        let items = get_item_types()
+            .map(|item_type| {
+                let stacks = slots.iter().filter(|s| s.get_item_type() == item_type);
+                ItemTotal {
+                    item: item_type.get_name(),
+                    amount: stacks.clone().map(|s| s.get_amount() as usize).sum(),
+                    slots: stacks.count(),
+                }
+            })
+            .collect();
+
+        WorldStatistics {
+            world_name: self.save_manager.world_info.world_name.clone(),
+            block_count,
+            solid_block_count,
+            solid_percentage: get_percentage(solid_block_count, block_count),
+            blocks,
+            height_histogram,
+            empty_columns,
+            chunks,
+            file_size,
+            compression_ratio: block_count as f32 / file_size as f32,
+            items,
+            used_slots: slots
+                .iter()
+                .filter(|s| s.get_item_type() != ItemType::Air)
+                .count(),
+            slot_count: slots.len(),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use nalgebra::Vector3;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::constants::{BlockType, ItemType},
+    world_editor::{inventory::get_item_types, BlockChange, World},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SurvivalEditError {
+    Unbreakable {
+        pos: Vector3<isize>,
+        block_type: BlockType,
+    },
+    NotPlaceable {
+        block_type: BlockType,
+    },
+    NotEnoughItems {
+        item_type: ItemType,
+        needed: u32,
+        available: u32,
+    },
+}
+
+impl SurvivalEditError {
+    pub fn get_description(&self) -> String {
+        match self {
+            SurvivalEditError::Unbreakable { pos, block_type } => format!(
+                "The {block_type:?} block at x: {}, y: {}, z: {} can't be mined.",
+                pos.x, pos.y, pos.z
+            ),
+            SurvivalEditError::NotPlaceable { block_type } => {
+                format!("No item can place {block_type:?} blocks.")
+            }
+            SurvivalEditError::NotEnoughItems {
+                item_type,
+                needed,
+                available,
+            } => format!(
+                "The edit needs {needed} {item_type:?} but the player only has {available}."
+            ),
+        }
+    }
+}
+
+/// The items gained and spent by an edit in survival-fair mode.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SurvivalReport {
+    pub drops: Vec<(ItemType, u32)>,
+    pub consumed: Vec<(ItemType, u32)>,
+    /// Drops that didn't fit in the inventory
+    pub lost_drops: u32,
+}
+
+impl SurvivalReport {
+    pub fn get_description(&self) -> String {
+        let format_counts = |counts: &[(ItemType, u32)]| {
+            if counts.is_empty() {
+                return "nothing".to_string();
+            }
+            counts
+                .iter()
+                .map(|(item_type, count)| format!("{count} {item_type:?}"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        let mut description = format!(
+            "Mined: {}. Used: {}.",
+            format_counts(&self.drops),
+            format_counts(&self.consumed)
+        );
+        if self.lost_drops > 0 {
+            description += &format!(" {} items didn't fit in the inventory.", self.lost_drops);
+        }
+
+        description
+    }
+}
+
+fn add_count(counts: &mut Vec<(ItemType, u32)>, item_type: ItemType, amount: u32) {
+    match counts.iter_mut().find(|(i, _)| *i == item_type) {
+        Some((_, count)) => *count += amount,
+        None => counts.push((item_type, amount)),
+    }
+}
+
+fn get_count(counts: &[(ItemType, u32)], item_type: ItemType) -> u32 {
+    counts
+        .iter()
+        .find(|(i, _)| *i == item_type)
+        .map_or(0, |(_, count)| *count)
+}
+
+/// The item the player needs to place a block.
+pub fn get_placing_item_type(block_type: BlockType) -> Option<ItemType> {
+    get_item_types().find(|item_type| item_type.get_matching_block_type() == Some(block_type))
+}
+
+impl World {
+    /// The number of items of this type in the inventory. Creative slots are infinite.
+    pub fn count_items(&self, item_type: ItemType) -> u32 {
+        let slots = self.get_inventory().get_all_slots();
+        let slots = slots.iter().filter(|s| s.get_item_type() == item_type);
+
+        if slots.clone().any(|s| s.creative_slot) {
+            u32::MAX
+        } else {
+            slots.map(|s| s.get_amount() as u32).sum()
+        }
+    }
+
+    /// Remove items from the inventory, starting with the last slots.
+    fn remove_items(&mut self, item_type: ItemType, mut amount: u32) {
+        let inventory = &mut self.save_manager.player_data.inventory;
+
+        for slot in (0..inventory.get_all_slots().len()).rev() {
+            let item_stack = *inventory.get_ref_to_slot(slot).unwrap();
+            if item_stack.get_item_type() != item_type || item_stack.creative_slot {
+                continue;
+            }
+
+            while amount > 0 && inventory.get_ref_to_slot(slot).unwrap().get_amount() > 0 {
+                inventory.take_one(slot);
+                amount -= 1;
+            }
+        }
+    }
+
+    /// The items dropped and consumed by the changes. Fails if a block can't be mined or placed, or if the player
+    /// doesn't have enough items, counting the drops of the same edit.
+    pub fn get_survival_cost(
+        &self,
+        changes: &[BlockChange],
+    ) -> Result<SurvivalReport, SurvivalEditError> {
+        let mut report = SurvivalReport::default();
+
+        for change in changes {
+            if !change.old_block.is_air() {
+                if change.old_block.get_hardness() < 0. {
+                    return Err(SurvivalEditError::Unbreakable {
+                        pos: change.pos,
+                        block_type: change.old_block,
+                    });
+                }
+                add_count(
+                    &mut report.drops,
+                    change.old_block.get_dropped_item_type(),
+                    1,
+                );
+            }
+
+            if !change.new_block.is_air() {
+                let item_type = get_placing_item_type(change.new_block).ok_or(
+                    SurvivalEditError::NotPlaceable {
+                        block_type: change.new_block,
+                    },
+                )?;
+                add_count(&mut report.consumed, item_type, 1);
+            }
+        }
+
+        for (item_type, needed) in &report.consumed {
+            let available = self
+                .count_items(*item_type)
+                .saturating_add(get_count(&report.drops, *item_type));
+            if available < *needed {
+                return Err(SurvivalEditError::NotEnoughItems {
+                    item_type: *item_type,
+                    needed: *needed,
+                    available,
+                });
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Take the cost of the changes from the inventory and add the drops with `add_item_stack`.
+    pub fn pay_for_changes(
+        &mut self,
+        changes: &[BlockChange],
+    ) -> Result<SurvivalReport, SurvivalEditError> {
+        let mut report = self.get_survival_cost(changes)?;
+
+        for item_type in get_item_types() {
+            let dropped = get_count(&report.drops, item_type);
+            let consumed = get_count(&report.consumed, item_type);
+
+            if consumed > dropped {
+                self.remove_items(item_type, consumed - dropped);
+            } else if dropped > consumed {
+                report.lost_drops += self.give_item(item_type, dropped - consumed);
+            }
+        }
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,1187 @@
+/* Behaviour tests of the world editing operations, on empty worlds and on the v0.1.3 fixtures */
+
+use std::{
+    env,
+    f32::consts::{FRAC_PI_2, TAU},
+    fs,
+    path::PathBuf,
+};
+
+use lz4_flex::decompress_size_prepended;
+use nalgebra::Vector3;
+use numcraft_format::{format::save_manager::optimize_world, limits::MAX_WORLD_NAME_SIZE};
+
+use crate::{
+    world_converter::{
+        dimensions::WorldDimensions,
+        numcraft_v0_1_3::{
+            constants::{BlockType, ItemType},
+            inventory::ItemStack,
+            save_manager::{GameMode, SaveManager},
+        },
+    },
+    world_editor::{
+        compact_changes,
+        fill::ReplaceOptions,
+        history::EditHistory,
+        metadata::{check_world_name, WorldNameError},
+        paint::get_line,
+        player::{Landmark, TeleportError},
+        query::BlockQuery,
+        script::{parse_script, ScriptError},
+        sculpt::{Brush, BrushKind, ColumnChange},
+        survival::{SurvivalEditError, SurvivalReport},
+        validate::WorldProblem,
+        BlockBox, BlockChange, World,
+    },
+};
+
+fn new_world() -> World {
+    World::new_empty(SaveManager::new())
+}
+
+fn load_fixture(kind: &str) -> World {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join(format!("numcraft-format/tests/fixtures/v0_1_3_{kind}.ncw"));
+    World::load(&fs::read(path).unwrap()).unwrap()
+}
+
+/// An empty world with Grass, Dirt and Stone layers up to `height` in every column.
+fn flat_world(height: isize) -> World {
+    let mut world = new_world();
+    for (x, z) in world.iter_columns().collect::<Vec<_>>() {
+        world.set_ground_height(&ColumnChange {
+            x,
+            z,
+            old_height: 0,
+            new_height: height,
+        });
+    }
+    world
+}
+
+#[test]
+fn statistics_add_up() {
+    for kind in ["empty", "built", "survival", "creative", "max_inventory"] {
+        let mut world = load_fixture(kind);
+        let statistics = world.get_statistics();
+        let size = world.get_size();
+
+        assert_eq!(statistics.block_count, (size.x * size.y * size.z) as usize);
+        assert_eq!(
+            statistics.blocks.iter().map(|b| b.count).sum::<usize>(),
+            statistics.block_count
+        );
+        assert_eq!(
+            statistics.height_histogram.iter().sum::<usize>() + statistics.empty_columns,
+            (size.x * size.z) as usize
+        );
+        assert_eq!(
+            statistics.chunks.len(),
+            world.get_dimensions().get_chunk_count()
+        );
+        assert_eq!(
+            statistics.items.iter().map(|i| i.slots).sum::<usize>(),
+            statistics.used_slots
+        );
+        assert_eq!(statistics.file_size, world.get_raw().len());
+    }
+}
+
+#[test]
+fn statistics_follow_unsaved_edits() {
+    let mut world = load_fixture("empty");
+    let before = world.get_statistics();
+
+    let pos = Vector3::new(5, world.get_size().y - 1, 7);
+    world.set_block(pos, BlockType::Stone);
+    let after = world.get_statistics();
+
+    assert_eq!(after.solid_block_count, before.solid_block_count + 1);
+    assert_eq!(
+        after.height_histogram.last(),
+        before
+            .height_histogram
+            .last()
+            .map(|count| count + 1)
+            .as_ref()
+    );
+    assert_eq!(after.file_size, world.get_raw().len());
+}
+
+#[test]
+fn statistics_read_the_sizes_of_the_saved_file() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("numcraft-format/tests/fixtures/v0_1_3_built.ncw");
+    let optimized = optimize_world(&fs::read(path).unwrap(), WorldDimensions::V0_1_X).unwrap();
+    let world = World::load(&optimized).unwrap();
+    let statistics = world.get_statistics();
+
+    assert_eq!(statistics.file_size, optimized.len());
+    let header_size = 2 + u16::from_be_bytes([optimized[0], optimized[1]]) as usize;
+    let content = decompress_size_prepended(&optimized[header_size..]).unwrap();
+    for (i, chunk) in statistics.chunks.iter().enumerate() {
+        let size = u16::from_be_bytes([content[i * 2], content[i * 2 + 1]]) as usize;
+        assert_eq!(chunk.compressed_size, size, "{:?}", chunk.pos);
+    }
+}
+
+#[test]
+fn statistics_are_exported() {
+    let mut world = load_fixture("built");
+    world.rename("Class, \"group 2\"").unwrap();
+    let statistics = world.get_statistics();
+
+    let csv = statistics.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(lines.next(), Some("section,name,value"));
+    assert_eq!(lines.next(), Some("world,name,\"Class, \"\"group 2\"\"\""));
+    assert!(csv.lines().all(|line| line.split(',').count() >= 3));
+
+    let json: serde_json::Value = serde_json::from_str(&statistics.to_json()).unwrap();
+    assert_eq!(json["world_name"], "Class, \"group 2\"");
+    assert_eq!(
+        json["blocks"].as_array().unwrap().len(),
+        statistics.blocks.len()
+    );
+    assert_eq!(json["file_size"], statistics.file_size);
+}
+
+#[test]
+fn fill_counts_the_changed_blocks() {
+    let mut world = new_world();
+    let area = BlockBox::new(Vector3::new(2, 2, 2), Vector3::new(3, 3, 3));
+
+    assert_eq!(world.fill(&area, BlockType::Stone), 8);
+    assert_eq!(world.fill(&area, BlockType::Stone), 0);
+    assert_eq!(
+        world.get_block(Vector3::new(3, 3, 3)),
+        Some(BlockType::Stone)
+    );
+
+    // Only the part inside the world is filled
+    let outside = BlockBox::new(Vector3::new(-5, 0, 0), Vector3::new(0, 0, 0));
+    assert_eq!(world.fill(&outside, BlockType::Dirt), 1);
+}
+
+#[test]
+fn replace_follows_its_options() {
+    let mut world = new_world();
+    let cube = BlockBox::new(Vector3::new(4, 4, 4), Vector3::new(6, 6, 6));
+    world.fill(&cube, BlockType::Stone);
+
+    let exposed = ReplaceOptions {
+        only_exposed: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        world.replace(&cube, BlockType::Stone, BlockType::Dirt, &exposed),
+        26
+    );
+    assert_eq!(
+        world.get_block(Vector3::new(5, 5, 5)),
+        Some(BlockType::Stone)
+    );
+
+    let never = ReplaceOptions {
+        probability: 0.,
+        ..Default::default()
+    };
+    assert_eq!(
+        world.replace(&cube, BlockType::Dirt, BlockType::Sand, &never),
+        0
+    );
+
+    let half = ReplaceOptions {
+        probability: 0.5,
+        seed: 7,
+        ..Default::default()
+    };
+    let mut copy = new_world();
+    copy.fill(&cube, BlockType::Stone);
+    copy.replace(&cube, BlockType::Stone, BlockType::Dirt, &exposed);
+    let replaced = world.replace(&cube, BlockType::Dirt, BlockType::Sand, &half);
+    assert!(replaced > 0 && replaced < 26);
+    // The same seed replaces the same blocks
+    copy.replace(&cube, BlockType::Dirt, BlockType::Sand, &half);
+    assert!(cube
+        .iter()
+        .all(|pos| copy.get_block(pos) == world.get_block(pos)));
+}
+
+#[test]
+fn hollow_and_walls_draw_the_sides() {
+    let mut world = new_world();
+    let area = BlockBox::new(Vector3::new(1, 1, 1), Vector3::new(3, 3, 3));
+
+    world.fill(&area, BlockType::Dirt);
+    // Only the center is emptied, the shell is turned to stone
+    assert_eq!(world.hollow(&area, BlockType::Stone), 27);
+    assert!(world.is_air(Vector3::new(2, 2, 2)));
+    assert_eq!(
+        world.get_block(Vector3::new(1, 2, 2)),
+        Some(BlockType::Stone)
+    );
+    assert_eq!(world.hollow(&area, BlockType::Stone), 0);
+
+    let mut world = new_world();
+    assert_eq!(world.walls(&area, BlockType::Planks), 24);
+    assert!(world.is_air(Vector3::new(2, 1, 2)));
+    assert!(world.is_air(Vector3::new(2, 3, 2)));
+}
+
+#[test]
+fn hollow_and_walls_are_script_commands() {
+    let mut world = new_world();
+    let script =
+        parse_script("hollow 1 1 1 to 3 3 3 with Stone\nwalls 5 1 5 to 7 3 7 with Planks").unwrap();
+
+    let effects = world.run_script(&script, false);
+    assert_eq!(
+        effects[0].description,
+        "Hollow from 1 1 1 to 3 3 3 with Stone"
+    );
+    assert!(effects.iter().all(|effect| !effect.failed));
+    assert_eq!(
+        world.get_block(Vector3::new(1, 2, 2)),
+        Some(BlockType::Stone)
+    );
+    assert!(world.is_air(Vector3::new(2, 2, 2)));
+    assert_eq!(
+        world.get_block(Vector3::new(5, 2, 6)),
+        Some(BlockType::Planks)
+    );
+    assert!(world.is_air(Vector3::new(6, 3, 6)));
+}
+
+#[test]
+fn the_outside_of_the_world_is_solid() {
+    let mut world = new_world();
+    let corner = Vector3::new(0, 0, 0);
+    world.set_block(corner, BlockType::Stone);
+    for pos in [
+        Vector3::new(1, 0, 0),
+        Vector3::new(0, 1, 0),
+        Vector3::new(0, 0, 1),
+    ] {
+        world.set_block(pos, BlockType::Stone);
+    }
+    assert!(!world.is_exposed(corner));
+
+    // A block against a side of the world leans on it
+    world.set_block(Vector3::new(0, 10, 10), BlockType::Stone);
+    world.set_block(Vector3::new(10, 10, 10), BlockType::Stone);
+    assert!(!world.is_floating(Vector3::new(0, 10, 10)));
+    assert!(world.is_floating(Vector3::new(10, 10, 10)));
+}
+
+#[test]
+fn script_errors_point_at_the_line() {
+    assert_eq!(
+        parse_script("give 64 Planks\njump").err(),
+        Some(ScriptError::UnknownCommand {
+            line: 2,
+            command: "jump".to_string()
+        })
+    );
+    assert_eq!(
+        parse_script("# Nothing to do\n\nreplace Sand with").err(),
+        Some(ScriptError::MissingArgument {
+            line: 3,
+            expected: "a block name"
+        })
+    );
+    assert_eq!(
+        parse_script("replace Sand by Stone").err(),
+        Some(ScriptError::InvalidArgument {
+            line: 1,
+            argument: "by".to_string(),
+            expected: "with"
+        })
+    );
+    assert_eq!(
+        parse_script("give 64 Diamond").err(),
+        Some(ScriptError::InvalidArgument {
+            line: 1,
+            argument: "Diamond".to_string(),
+            expected: "an item name"
+        })
+    );
+    assert_eq!(
+        parse_script("repair border now").err(),
+        Some(ScriptError::InvalidArgument {
+            line: 1,
+            argument: "now".to_string(),
+            expected: "the end of the command"
+        })
+    );
+    assert_eq!(
+        parse_script("rename \"Class, 2").err(),
+        Some(ScriptError::UnclosedQuote { line: 1 })
+    );
+
+    let statements = parse_script("rename \"Class, #2\", seed 4 # comment\nteleport safe").unwrap();
+    assert_eq!(
+        statements.iter().map(|s| s.line).collect::<Vec<_>>(),
+        [1, 1, 2]
+    );
+}
+
+#[test]
+fn safe_spawn_is_the_closest_place_to_stand() {
+    let mut world = new_world();
+    assert_eq!(world.find_safe_spawn(Vector3::new(5., 5., 5.)), None);
+
+    let size = world.get_size();
+    world.fill(
+        &BlockBox::new(
+            Vector3::new(0, 3, 0),
+            Vector3::new(size.x - 1, 3, size.z - 1),
+        ),
+        BlockType::Stone,
+    );
+    assert_eq!(
+        world.find_safe_spawn(Vector3::new(10.2, 20., 10.7)),
+        Some(Vector3::new(10, 4, 10))
+    );
+
+    // There is no room for the player under a block
+    world.set_block(Vector3::new(10, 5, 10), BlockType::Stone);
+    assert!(!world.is_safe_spawn(Vector3::new(10, 4, 10)));
+    assert_ne!(
+        world.find_safe_spawn(Vector3::new(10.5, 4., 10.5)),
+        Some(Vector3::new(10, 4, 10))
+    );
+    // A position that isn't finite looks from the center of the world
+    assert!(world
+        .find_safe_spawn(Vector3::new(f32::NAN, 0., 0.))
+        .is_some());
+}
+
+#[test]
+fn changes_are_compacted() {
+    let change = |x, old_block, new_block| BlockChange {
+        pos: Vector3::new(x, 0, 0),
+        old_block,
+        new_block,
+    };
+
+    let changes = [
+        change(1, BlockType::Air, BlockType::Stone),
+        change(2, BlockType::Dirt, BlockType::Sand),
+        change(1, BlockType::Stone, BlockType::Dirt),
+        change(2, BlockType::Sand, BlockType::Dirt),
+    ];
+    assert_eq!(
+        compact_changes(&changes),
+        [change(1, BlockType::Air, BlockType::Dirt)]
+    );
+}
+
+#[test]
+fn long_world_names_are_only_a_warning() {
+    let mut world = new_world();
+    world
+        .save_manager
+        .set_world_name("A world with a very long name");
+
+    let problems: Vec<WorldProblem> = world
+        .validate()
+        .into_iter()
+        .filter(|problem| !matches!(problem, WorldProblem::BrokenBorder { .. }))
+        .collect();
+    assert_eq!(problems, [WorldProblem::LongWorldName { length: 29 }]);
+    assert!(problems[0].is_warning());
+}
+
+#[test]
+fn the_border_is_rebuilt_after_edits_when_enforced() {
+    let mut world = new_world();
+    world.repair_border();
+    let wall = BlockBox::new(Vector3::new(0, 0, 5), Vector3::new(0, 5, 5));
+
+    // Edits are free to break the border by default
+    let (_, report) = world
+        .edit("Fill", |world| world.fill(&wall, BlockType::Stone))
+        .unwrap();
+    assert!(report.border.replaced.is_empty());
+    assert_eq!(world.get_border_gaps().len(), 6);
+
+    world.enforce_border = true;
+    let (changed, report) = world
+        .edit("Fill", |world| world.fill(&wall, BlockType::Dirt))
+        .unwrap();
+    assert_eq!(changed, 6);
+    assert_eq!(report.border.replaced.len(), 6);
+    assert!(world.get_border_gaps().is_empty());
+
+    // The repair is part of the edit, one undo removes both
+    world.undo();
+    assert_eq!(
+        world.get_block(Vector3::new(0, 3, 5)),
+        Some(BlockType::Stone)
+    );
+}
+
+#[test]
+fn merging_keeps_the_creative_slots() {
+    let mut world = new_world();
+    world.save_manager.set_gamemode(GameMode::Creative);
+    let inventory = &mut world.save_manager.player_data.inventory;
+    inventory.replace_slot_item_stack(0, ItemStack::new(ItemType::StoneBlock, 64, true));
+    inventory.replace_slot_item_stack(1, ItemStack::new(ItemType::StoneBlock, 10, false));
+    inventory.replace_slot_item_stack(2, ItemStack::new(ItemType::DirtBlock, 5, false));
+    inventory.replace_slot_item_stack(3, ItemStack::new(ItemType::StoneBlock, 60, false));
+
+    world.merge_inventory_stacks();
+
+    let slots = world.get_inventory().get_all_slots();
+    assert_eq!(slots[0], ItemStack::new(ItemType::StoneBlock, 64, true));
+    assert_eq!(slots[1], ItemStack::new(ItemType::StoneBlock, 64, false));
+    assert_eq!(slots[2], ItemStack::new(ItemType::DirtBlock, 5, false));
+    assert_eq!(slots[3], ItemStack::new(ItemType::StoneBlock, 6, false));
+    assert!(slots[4..]
+        .iter()
+        .all(|s| s.get_item_type() == ItemType::Air));
+}
+
+#[test]
+fn a_stroke_is_one_command() {
+    let mut world = new_world();
+    let cells: Vec<Vector3<isize>> = (3..9).map(|x| Vector3::new(x, 1, 4)).collect();
+
+    world
+        .edit("Pencil", |world| {
+            cells
+                .iter()
+                .filter(|pos| world.set_block(**pos, BlockType::Planks))
+                .count()
+        })
+        .unwrap();
+    assert_eq!(world.get_history().get_commands().len(), 1);
+    assert_eq!(world.get_history().get_commands()[0].changes.len(), 6);
+
+    world.undo();
+    assert!(cells.iter().all(|pos| world.is_air(*pos)));
+}
+
+#[test]
+fn dry_runs_keep_the_redo_stack() {
+    let mut world = new_world();
+    let first = Vector3::new(2, 1, 2);
+    let second = Vector3::new(3, 1, 2);
+    world
+        .edit("First", |world| world.set_block(first, BlockType::Stone))
+        .unwrap();
+    world
+        .edit("Second", |world| world.set_block(second, BlockType::Stone))
+        .unwrap();
+    world.undo();
+
+    let script = parse_script("fill 0 0 0 to 5 5 5 with Sand\ngive 64 Planks").unwrap();
+    let effects = world.run_script(&script, true);
+    assert!(effects.iter().all(|effect| !effect.failed));
+
+    assert_eq!(world.get_block(first), Some(BlockType::Stone));
+    assert!(world.is_air(second));
+    assert!(world
+        .get_inventory()
+        .get_all_slots()
+        .iter()
+        .all(|s| s.get_item_type() == ItemType::Air));
+    assert_eq!(world.get_history().get_position(), 1);
+
+    assert!(world.redo());
+    assert_eq!(world.get_block(second), Some(BlockType::Stone));
+    assert_eq!(world.get_history().get_commands().len(), 2);
+}
+
+#[test]
+fn world_names_are_limited_by_the_format() {
+    let name = "a".repeat(MAX_WORLD_NAME_SIZE);
+    assert_eq!(check_world_name(&name), Ok(()));
+    assert_eq!(
+        check_world_name(&format!("{name}a")),
+        Err(WorldNameError::TooLong {
+            size: MAX_WORLD_NAME_SIZE + 1
+        })
+    );
+
+    let mut world = new_world();
+    world.save_manager.set_world_name(&format!("{name}a"));
+    assert!(world.validate().contains(&WorldProblem::WorldNameTooLarge {
+        size: MAX_WORLD_NAME_SIZE + 1
+    }));
+}
+
+#[test]
+fn brush_previews_leave_the_world_untouched() {
+    let mut world = flat_world(5);
+    let raw = world.get_raw();
+    let brush = Brush {
+        kind: BrushKind::Raise,
+        radius: 2.,
+        strength: 1.,
+    };
+
+    let preview = world.get_brush_preview((10, 10), &brush);
+    assert!(!preview.is_empty());
+    assert_eq!(world.get_raw(), raw);
+    assert!(world.get_history().get_commands().is_empty());
+
+    // The border walls are never sculpted
+    let corner = world.get_brush_preview((1, 1), &brush);
+    assert!(!corner.is_empty());
+    assert!(corner.iter().all(|change| change.x > 0 && change.z > 0));
+}
+
+#[test]
+fn raising_fades_with_the_distance() {
+    let mut world = flat_world(5);
+    let brush = Brush {
+        kind: BrushKind::Raise,
+        radius: 2.,
+        strength: 1.,
+    };
+
+    assert!(world.apply_brush((10, 10), &brush) > 0);
+    assert_eq!(world.get_ground_height(10, 10), 7);
+    assert_eq!(world.get_ground_height(11, 10), 6);
+    assert_eq!(world.get_ground_height(12, 10), 6);
+    assert_eq!(world.get_ground_height(13, 10), 5);
+
+    // The old surface is buried under the new one
+    assert_eq!(
+        world.get_block(Vector3::new(10, 6, 10)),
+        Some(BlockType::Grass)
+    );
+    assert_eq!(
+        world.get_block(Vector3::new(10, 4, 10)),
+        Some(BlockType::Dirt)
+    );
+
+    // A weaker brush only reaches the columns close to the center
+    let mut world = flat_world(5);
+    let weak = Brush {
+        strength: 0.5,
+        ..brush
+    };
+    world.apply_brush((10, 10), &weak);
+    assert_eq!(world.get_ground_height(10, 10), 6);
+    assert_eq!(world.get_ground_height(12, 10), 5);
+}
+
+#[test]
+fn lowering_keeps_the_floor_and_the_grass() {
+    let mut world = flat_world(5);
+    let brush = Brush {
+        kind: BrushKind::Lower,
+        radius: 2.,
+        strength: 1.,
+    };
+
+    world.apply_brush((10, 10), &brush);
+    assert_eq!(world.get_ground_height(10, 10), 3);
+    assert_eq!(
+        world.get_block(Vector3::new(10, 2, 10)),
+        Some(BlockType::Grass)
+    );
+    assert!(world.is_air(Vector3::new(10, 3, 10)));
+
+    // The floor of the world is never dug
+    let deep = Brush {
+        radius: 10.,
+        ..brush
+    };
+    world.apply_brush((10, 10), &deep);
+    assert_eq!(world.get_ground_height(10, 10), 1);
+    assert_eq!(
+        world.get_block(Vector3::new(10, 0, 10)),
+        Some(BlockType::Stone)
+    );
+}
+
+#[test]
+fn smoothing_and_flattening_level_the_ground() {
+    let mut world = flat_world(5);
+    world.set_ground_height(&ColumnChange {
+        x: 10,
+        z: 10,
+        old_height: 5,
+        new_height: 9,
+    });
+    let smooth = Brush {
+        kind: BrushKind::Smooth,
+        radius: 0.5,
+        strength: 1.,
+    };
+    world.apply_brush((10, 10), &smooth);
+    assert_eq!(world.get_ground_height(10, 10), 5);
+
+    let flatten = Brush {
+        kind: BrushKind::Flatten { level: 8 },
+        radius: 1.,
+        strength: 1.,
+    };
+    world.apply_brush((10, 10), &flatten);
+    assert_eq!(world.get_ground_height(10, 10), 8);
+    let side = world.get_ground_height(11, 10);
+    assert!(side > 5 && side < 8, "{side}");
+    assert_eq!(world.get_ground_height(12, 10), 5);
+}
+
+#[test]
+fn survival_edits_are_paid_with_the_inventory() {
+    let mut world = new_world();
+    world.save_manager.set_gamemode(GameMode::Survival);
+    world.survival_fair = true;
+    world
+        .save_manager
+        .player_data
+        .inventory
+        .replace_slot_item_stack(0, ItemStack::new(ItemType::PlanksBlock, 10, false));
+    let floor = BlockBox::new(Vector3::new(2, 1, 2), Vector3::new(3, 1, 3));
+    world.fill(&floor, BlockType::Stone);
+
+    let (changed, report) = world
+        .edit("Fill", |world| world.fill(&floor, BlockType::Planks))
+        .unwrap();
+    assert_eq!(changed, 4);
+    assert_eq!(
+        report.survival,
+        Some(SurvivalReport {
+            drops: vec![(ItemType::CobblestoneBlock, 4)],
+            consumed: vec![(ItemType::PlanksBlock, 4)],
+            lost_drops: 0,
+        })
+    );
+    assert_eq!(world.count_items(ItemType::PlanksBlock), 6);
+    assert_eq!(world.count_items(ItemType::CobblestoneBlock), 4);
+    assert_eq!(world.get_history().get_commands().len(), 1);
+}
+
+#[test]
+fn refused_survival_edits_change_nothing() {
+    let mut world = new_world();
+    world.save_manager.set_gamemode(GameMode::Survival);
+    world.survival_fair = true;
+    world
+        .save_manager
+        .player_data
+        .inventory
+        .replace_slot_item_stack(0, ItemStack::new(ItemType::PlanksBlock, 6, false));
+    world.set_block(Vector3::new(8, 1, 8), BlockType::Border);
+    let raw = world.get_raw();
+    let slots = world.get_inventory().get_all_slots().clone();
+
+    let wall = BlockBox::new(Vector3::new(2, 1, 2), Vector3::new(5, 5, 2));
+    assert_eq!(
+        world
+            .edit("Fill", |world| world.fill(&wall, BlockType::Planks))
+            .err(),
+        Some(SurvivalEditError::NotEnoughItems {
+            item_type: ItemType::PlanksBlock,
+            needed: 20,
+            available: 6,
+        })
+    );
+    assert_eq!(
+        world
+            .edit("Pencil", |world| world
+                .set_block(Vector3::new(8, 1, 8), BlockType::Air))
+            .err(),
+        Some(SurvivalEditError::Unbreakable {
+            pos: Vector3::new(8, 1, 8),
+            block_type: BlockType::Border,
+        })
+    );
+
+    assert_eq!(world.get_raw(), raw);
+    assert_eq!(*world.get_inventory().get_all_slots(), slots);
+    assert!(world.get_history().get_commands().is_empty());
+}
+
+#[test]
+fn queries_find_the_placed_blocks() {
+    let mut world = new_world();
+    world.set_block(Vector3::new(1, 1, 1), BlockType::Stone);
+    world.set_block(Vector3::new(1, 2, 1), BlockType::Dirt);
+    world.set_block(Vector3::new(10, 10, 10), BlockType::Stone);
+    world.set_block(Vector3::new(20, 5, 3), BlockType::Sand);
+
+    let sorted = |mut positions: Vec<Vector3<isize>>| {
+        positions.sort_by_key(|pos| (pos.x, pos.y, pos.z));
+        positions
+    };
+    assert_eq!(
+        sorted(world.run_query(BlockQuery::BlockType(BlockType::Stone))),
+        [Vector3::new(1, 1, 1), Vector3::new(10, 10, 10)]
+    );
+    assert_eq!(
+        sorted(world.run_query(BlockQuery::FloatingBlocks)),
+        [Vector3::new(10, 10, 10), Vector3::new(20, 5, 3)]
+    );
+    // Ordered by z then x
+    assert_eq!(
+        world.run_query(BlockQuery::HighestBlocks),
+        [
+            Vector3::new(1, 2, 1),
+            Vector3::new(20, 5, 3),
+            Vector3::new(10, 10, 10)
+        ]
+    );
+
+    let height_map = world.get_height_map();
+    let size = world.get_size();
+    assert_eq!(height_map.len(), (size.x * size.z) as usize);
+    assert_eq!(height_map[(size.x + 1) as usize], Some(2));
+    assert_eq!(height_map.iter().filter(|h| h.is_some()).count(), 3);
+
+    let empty_chunks = world.run_query(BlockQuery::EmptyChunks);
+    assert_eq!(
+        empty_chunks.len(),
+        world.get_dimensions().get_chunk_count() - 3
+    );
+    for chunk in [
+        Vector3::new(0, 0, 0),
+        Vector3::new(1, 1, 1),
+        Vector3::new(2, 0, 0),
+    ] {
+        assert!(!empty_chunks.contains(&chunk));
+    }
+}
+
+#[test]
+fn teleports_are_checked() {
+    let mut world = flat_world(5);
+
+    assert_eq!(world.teleport_player(Vector3::new(10.5, 5., 10.5)), Ok(()));
+    assert_eq!(world.save_manager.player_data.pos, (10.5, 5., 10.5));
+    // The head may be above the top of the world
+    assert_eq!(world.teleport_player(Vector3::new(3.5, 31., 3.5)), Ok(()));
+
+    for (pos, error) in [
+        (
+            Vector3::new(10.5, 3.5, 10.5),
+            TeleportError::InsideBlock {
+                block_pos: Vector3::new(10, 3, 10),
+                block_type: BlockType::Dirt,
+            },
+        ),
+        (Vector3::new(-0.5, 10., 10.), TeleportError::OutsideWorld),
+        (Vector3::new(10., 32., 10.), TeleportError::OutsideWorld),
+        (Vector3::new(10., f32::NAN, 10.), TeleportError::NotFinite),
+    ] {
+        assert_eq!(world.teleport_player(pos), Err(error), "{pos:?}");
+    }
+    assert_eq!(world.save_manager.player_data.pos, (3.5, 31., 3.5));
+
+    assert_eq!(
+        world.get_landing_position(Vector3::new(7.5, 20., 7.5)),
+        Some(Vector3::new(7, 5, 7))
+    );
+    assert_eq!(
+        new_world().get_landing_position(Vector3::new(7.5, 20., 7.5)),
+        None
+    );
+}
+
+#[test]
+fn player_rotations_are_kept_in_range() {
+    let mut world = new_world();
+
+    world.set_player_rotation(0.5, 1.);
+    assert_eq!(world.save_manager.player_data.rotation, (0.5, 1.));
+    world.set_player_rotation(3., -1.);
+    assert_eq!(
+        world.save_manager.player_data.rotation,
+        (FRAC_PI_2, TAU - 1.)
+    );
+    world.set_player_rotation(-3., TAU + 1.);
+    let (pitch, yaw) = world.save_manager.player_data.rotation;
+    assert_eq!(pitch, -FRAC_PI_2);
+    assert!((yaw - 1.).abs() < 1e-5, "{yaw}");
+    world.set_player_rotation(f32::NAN, f32::INFINITY);
+    assert_eq!(world.save_manager.player_data.rotation, (0., 0.));
+}
+
+#[test]
+fn landmarks_stand_on_the_ground() {
+    let mut world = flat_world(5);
+    world.fill(
+        &BlockBox::new(Vector3::new(20, 5, 20), Vector3::new(20, 9, 20)),
+        BlockType::Stone,
+    );
+    world.teleport_player(Vector3::new(3.5, 8., 3.5)).unwrap();
+
+    let landmark = |name: &str, feet_pos| Landmark {
+        name: name.to_string(),
+        feet_pos,
+    };
+    assert_eq!(
+        world.get_landmarks(),
+        [
+            landmark("World center", Vector3::new(16, 5, 16)),
+            landmark("Highest point", Vector3::new(20, 10, 20)),
+            landmark("Closest safe spawn", Vector3::new(3, 5, 3)),
+        ]
+    );
+
+    assert!(new_world().get_landmarks().is_empty());
+}
+
+fn survival_world(slots: &[(usize, ItemType, u8)]) -> World {
+    let mut world = new_world();
+    world.save_manager.set_gamemode(GameMode::Survival);
+    for (slot, item_type, amount) in slots {
+        world.set_inventory_slot(*slot, *item_type, *amount);
+    }
+    world
+}
+
+fn get_slots(world: &World) -> Vec<(ItemType, u8)> {
+    world
+        .get_inventory()
+        .get_all_slots()
+        .iter()
+        .map(|s| (s.get_item_type(), s.get_amount()))
+        .collect()
+}
+
+#[test]
+fn sorting_merges_then_orders_the_stacks() {
+    let mut world = survival_world(&[
+        (0, ItemType::PlanksBlock, 10),
+        (2, ItemType::StoneBlock, 5),
+        (3, ItemType::PlanksBlock, 60),
+        (5, ItemType::DirtBlock, 64),
+    ]);
+
+    world.sort_inventory();
+    let slots = get_slots(&world);
+    assert_eq!(
+        slots[..4],
+        [
+            (ItemType::StoneBlock, 5),
+            (ItemType::DirtBlock, 64),
+            (ItemType::PlanksBlock, 64),
+            (ItemType::PlanksBlock, 6),
+        ]
+    );
+    assert!(slots[4..].iter().all(|(i, _)| *i == ItemType::Air));
+}
+
+#[test]
+fn given_items_fill_the_stacks_then_the_empty_slots() {
+    let mut world = survival_world(&[(1, ItemType::StoneBlock, 60)]);
+    assert_eq!(world.give_item(ItemType::StoneBlock, 140), 0);
+    assert_eq!(
+        get_slots(&world)[..4],
+        [
+            (ItemType::StoneBlock, 64),
+            (ItemType::StoneBlock, 64),
+            (ItemType::StoneBlock, 64),
+            (ItemType::StoneBlock, 8),
+        ]
+    );
+    assert_eq!(world.count_items(ItemType::StoneBlock), 200);
+
+    // Only what fits is given
+    let mut world = new_world();
+    world.save_manager.set_gamemode(GameMode::Survival);
+    let slot_count = world.get_inventory().get_all_slots().len();
+    for slot in 0..slot_count - 1 {
+        world.set_inventory_slot(slot, ItemType::DirtBlock, 64);
+    }
+    world.set_inventory_slot(slot_count - 1, ItemType::StoneBlock, 60);
+    assert_eq!(world.give_item(ItemType::StoneBlock, 10), 6);
+    assert_eq!(world.count_items(ItemType::StoneBlock), 64);
+    assert_eq!(world.give_item(ItemType::SandBlock, 10), 10);
+    assert_eq!(world.count_items(ItemType::SandBlock), 0);
+
+    // Creative slots are infinite, a single slot is enough
+    let mut world = new_world();
+    world.save_manager.set_gamemode(GameMode::Creative);
+    assert_eq!(world.give_item(ItemType::LogBlock, 500), 0);
+    assert_eq!(world.give_item(ItemType::LogBlock, 500), 0);
+    assert_eq!(world.count_items(ItemType::LogBlock), u32::MAX);
+    assert_eq!(
+        get_slots(&world)
+            .iter()
+            .filter(|(i, _)| *i == ItemType::LogBlock)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn moved_stacks_are_swapped_or_merged() {
+    let mut world = survival_world(&[
+        (0, ItemType::StoneBlock, 10),
+        (1, ItemType::DirtBlock, 5),
+        (2, ItemType::StoneBlock, 60),
+    ]);
+
+    // Different items are swapped
+    world.move_inventory_item(0, 1);
+    assert_eq!(
+        get_slots(&world)[..2],
+        [(ItemType::DirtBlock, 5), (ItemType::StoneBlock, 10)]
+    );
+
+    // The same items are merged up to a full stack
+    world.move_inventory_item(1, 2);
+    assert_eq!(
+        get_slots(&world)[1..3],
+        [(ItemType::StoneBlock, 6), (ItemType::StoneBlock, 64)]
+    );
+
+    world.move_inventory_item(1, 5);
+    let slots = get_slots(&world);
+    assert_eq!(slots[1].0, ItemType::Air);
+    assert_eq!(slots[5], (ItemType::StoneBlock, 6));
+
+    // Slots outside of the inventory are ignored
+    world.move_inventory_item(0, slots.len());
+    assert_eq!(get_slots(&world), slots);
+}
+
+#[test]
+fn lines_are_continuous() {
+    let line = get_line(Vector3::new(0, 2, 0), Vector3::new(4, 2, 2));
+    assert_eq!(line.len(), 5);
+    assert_eq!(line.first(), Some(&Vector3::new(0, 2, 0)));
+    assert_eq!(line.last(), Some(&Vector3::new(4, 2, 2)));
+    assert!(line.windows(2).all(|pair| {
+        let step = pair[1] - pair[0];
+        step.x.abs() <= 1 && step.y == 0 && step.z.abs() <= 1
+    }));
+
+    assert_eq!(
+        get_line(Vector3::new(3, 0, 3), Vector3::new(0, 0, 0)),
+        (0..=3)
+            .rev()
+            .map(|i| Vector3::new(i, 0, i))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn lines_and_rectangles_stay_in_the_first_layer() {
+    let mut world = new_world();
+
+    assert_eq!(
+        world.paint_line(
+            Vector3::new(2, 3, 2),
+            Vector3::new(8, 7, 2),
+            BlockType::Stone
+        ),
+        7
+    );
+    assert_eq!(
+        world.paint_line(
+            Vector3::new(2, 3, 2),
+            Vector3::new(8, 7, 2),
+            BlockType::Stone
+        ),
+        0
+    );
+    assert_eq!(
+        world.get_block(Vector3::new(8, 3, 2)),
+        Some(BlockType::Stone)
+    );
+    assert!(world.is_air(Vector3::new(8, 7, 2)));
+
+    assert_eq!(
+        world.paint_rectangle(
+            Vector3::new(2, 1, 2),
+            Vector3::new(5, 9, 4),
+            BlockType::Sand
+        ),
+        12
+    );
+    assert_eq!(
+        world.get_block(Vector3::new(5, 1, 4)),
+        Some(BlockType::Sand)
+    );
+    assert!(world.is_air(Vector3::new(5, 2, 4)));
+}
+
+#[test]
+fn flood_fill_stops_at_other_blocks_and_at_the_sides() {
+    let mut world = new_world();
+    let corners = [
+        Vector3::new(2, 1, 2),
+        Vector3::new(6, 1, 2),
+        Vector3::new(6, 1, 6),
+        Vector3::new(2, 1, 6),
+    ];
+    for i in 0..4 {
+        world.paint_line(corners[i], corners[(i + 1) % 4], BlockType::Stone);
+    }
+
+    assert_eq!(
+        world.flood_fill_layer(Vector3::new(4, 1, 4), BlockType::Sand),
+        9
+    );
+    assert_eq!(
+        world.get_block(Vector3::new(3, 1, 5)),
+        Some(BlockType::Sand)
+    );
+    assert_eq!(
+        world.get_block(Vector3::new(2, 1, 4)),
+        Some(BlockType::Stone)
+    );
+    assert!(world.is_air(Vector3::new(1, 1, 4)));
+    assert!(world.is_air(Vector3::new(4, 2, 4)));
+
+    // Nothing to replace
+    assert_eq!(
+        world.flood_fill_layer(Vector3::new(4, 1, 4), BlockType::Sand),
+        0
+    );
+    assert_eq!(
+        world.flood_fill_layer(Vector3::new(-1, 1, 4), BlockType::Sand),
+        0
+    );
+
+    // An open layer is filled up to the sides of the world
+    let size = world.get_size();
+    assert_eq!(
+        world.flood_fill_layer(Vector3::new(0, 5, 0), BlockType::Dirt),
+        (size.x * size.z) as usize
+    );
+    assert!(world.is_air(Vector3::new(0, 6, 0)));
+}
+
+#[test]
+fn saved_histories_are_replayed_on_the_same_world() {
+    let mut world = load_fixture("built");
+    world
+        .edit("Fill", |world| {
+            world.fill(
+                &BlockBox::new(Vector3::new(2, 20, 2), Vector3::new(4, 20, 4)),
+                BlockType::Planks,
+            )
+        })
+        .unwrap();
+    world
+        .edit_state("Rename", |world| world.rename("Restored"))
+        .unwrap();
+    world
+        .edit("Pencil", |world| {
+            world.set_block(Vector3::new(20, 25, 20), BlockType::Leaves)
+        })
+        .unwrap();
+    world.undo();
+
+    let value = world.get_history().to_storage_string();
+    let history = EditHistory::from_storage_string(&value).unwrap();
+    assert_eq!(history.get_position(), 2);
+    assert_eq!(history.get_commands().len(), 3);
+
+    let mut restored = load_fixture("built");
+    assert!(restored.restore_history(history.clone()));
+    assert_eq!(restored.get_raw(), world.get_raw());
+    assert!(restored.redo());
+    assert_eq!(
+        restored.get_block(Vector3::new(20, 25, 20)),
+        Some(BlockType::Leaves)
+    );
+
+    // Another save file or an edited world keep their own history
+    let mut other = load_fixture("empty");
+    assert!(!other.restore_history(history.clone()));
+    assert!(other.get_history().is_empty());
+    let mut edited = load_fixture("built");
+    edited
+        .edit("Pencil", |world| {
+            world.set_block(Vector3::new(1, 25, 1), BlockType::Sand)
+        })
+        .unwrap();
+    assert!(!edited.restore_history(history));
+    assert_eq!(edited.get_history().get_commands().len(), 1);
+}
+
+#[test]
+fn broken_storage_strings_are_rejected() {
+    let value = new_world().get_history().to_storage_string();
+    assert!(EditHistory::from_storage_string(&value).is_some());
+
+    for value in ["", "abc", "zz", "0g", "é", &value[..value.len() - 2]] {
+        assert!(
+            EditHistory::from_storage_string(value).is_none(),
+            "{value:?}"
+        );
+    }
+}
+
+#[test]
+fn scripts_apply_their_statements() {
+    let mut world = new_world();
+    let script = parse_script(
+        "fill 1 1 1 to 3 1 3 with Stone, replace Stone with Dirt below y=2\n\
+         give 70 Planks\n\
+         teleport 5.5 2 5.5, teleport 2.5 1 2.5\n\
+         rename \"Class 2\"",
+    )
+    .unwrap();
+
+    let effects = world.run_script(&script, false);
+    assert_eq!(
+        effects
+            .iter()
+            .map(|effect| (effect.line, effect.failed))
+            .collect::<Vec<_>>(),
+        [
+            (1, false),
+            (1, false),
+            (2, false),
+            (3, false),
+            (3, true),
+            (4, false)
+        ]
+    );
+    assert_eq!(
+        world.get_block(Vector3::new(2, 1, 2)),
+        Some(BlockType::Dirt)
+    );
+    assert!(world
+        .run_query(BlockQuery::BlockType(BlockType::Stone))
+        .is_empty());
+    assert_eq!(world.count_items(ItemType::PlanksBlock), 70);
+    // The failed teleport leaves the player where the first one put it
+    assert_eq!(world.save_manager.player_data.pos, (5.5, 2., 5.5));
+    assert_eq!(world.save_manager.world_info.world_name, "Class 2");
+    // Each statement that changed something is its own command
+    assert_eq!(world.get_history().get_commands().len(), 5);
+}
+
+#[test]
+fn gamemode_scripts_keep_the_inventory_unless_asked() {
+    let mut world = survival_world(&[(0, ItemType::StoneBlock, 10)]);
+    let slots = get_slots(&world);
+
+    world.run_script(&parse_script("gamemode creative").unwrap(), false);
+    assert_eq!(world.save_manager.get_game_mode(), GameMode::Creative);
+    assert_eq!(get_slots(&world), slots);
+
+    world.run_script(
+        &parse_script("gamemode creative convert inventory").unwrap(),
+        false,
+    );
+    assert!(world
+        .get_inventory()
+        .get_all_slots()
+        .iter()
+        .any(|s| s.creative_slot));
+
+    assert_eq!(
+        parse_script("gamemode creative keep inventory").err(),
+        Some(ScriptError::InvalidArgument {
+            line: 1,
+            argument: "keep".to_string(),
+            expected: "the end of the command"
+        })
+    );
+}
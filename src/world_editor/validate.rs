@@ -0,0 +1,177 @@
+use nalgebra::Vector3;
+use numcraft_format::limits::MAX_WORLD_NAME_SIZE;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::{
+        constants::{save_manager::PLAYER_INVENTORY_SIZE, BlockType, ItemType},
+        save_manager::GameMode,
+    },
+    world_editor::World,
+};
+
+// About what one line of the worlds list of the calculator shows. This is not a limit of the game, so longer names
+// are only a warning
+pub const DISPLAYED_WORLD_NAME_LENGTH: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorldProblem {
+    BrokenBorder {
+        gaps: Vec<Vector3<isize>>,
+    },
+    PlayerOutsideWorld {
+        pos: Vector3<f32>,
+    },
+    PlayerInsideBlock {
+        block_pos: Vector3<isize>,
+        block_type: BlockType,
+    },
+    StackTooLarge {
+        slot: usize,
+        item_type: ItemType,
+        amount: u8,
+    },
+    CreativeSlotInSurvival {
+        slot: usize,
+    },
+    WorldNameTooLarge {
+        size: usize,
+    },
+    /// A warning: the name is saved, but the worlds list may cut it
+    LongWorldName {
+        length: usize,
+    },
+    WorldNameUnprintable {
+        character: char,
+    },
+    WrongInventorySize {
+        size: usize,
+    },
+}
+
+impl WorldProblem {
+    pub fn get_description(&self) -> String {
+        match self {
+            WorldProblem::BrokenBorder { gaps } => {
+                format!("The world border has {} missing blocks.", gaps.len())
+            }
+            WorldProblem::PlayerOutsideWorld { pos } => format!(
+                "The player is outside of the world (x: {:.1}, y: {:.1}, z: {:.1}).",
+                pos.x, pos.y, pos.z
+            ),
+            WorldProblem::PlayerInsideBlock {
+                block_pos,
+                block_type,
+            } => format!(
+                "The player is stuck inside a {:?} block (x: {}, y: {}, z: {}).",
+                block_type, block_pos.x, block_pos.y, block_pos.z
+            ),
+            WorldProblem::StackTooLarge {
+                slot,
+                item_type,
+                amount,
+            } => format!(
+                "Slot {slot} contains {amount} {item_type:?}, the maximum is {}.",
+                item_type.get_max_stack_amount()
+            ),
+            WorldProblem::CreativeSlotInSurvival { slot } => {
+                format!("Slot {slot} is an infinite creative stack in a Survival world.")
+            }
+            WorldProblem::WorldNameTooLarge { size } => format!(
+                "The world name is {size} bytes long, the maximum is {MAX_WORLD_NAME_SIZE} bytes."
+            ),
+            WorldProblem::LongWorldName { length } => format!(
+                "The world name is {length} characters long, the worlds list of the calculator may not show all of it."
+            ),
+            WorldProblem::WorldNameUnprintable { character } => {
+                format!("The world name contains {character:?} which the calculator can't display.")
+            }
+            WorldProblem::WrongInventorySize { size } => format!(
+                "The inventory has {size} slots instead of {PLAYER_INVENTORY_SIZE}."
+            ),
+        }
+    }
+
+    /// Warnings don't stop the world from being played.
+    pub fn is_warning(&self) -> bool {
+        matches!(self, WorldProblem::LongWorldName { .. })
+    }
+}
+
+/// The calculator font only contains the printable ASCII characters.
+pub fn is_displayable_character(character: char) -> bool {
+    character == ' ' || character.is_ascii_graphic()
+}
+
+pub fn get_player_block_pos(pos: Vector3<f32>) -> Vector3<isize> {
+    pos.map(|c| libm::floorf(c) as isize)
+}
+
+impl World {
+    /// Check the world for problems. Nothing is modified.
+    pub fn validate(&self) -> Vec<WorldProblem> {
+        let mut problems = Vec::new();
+
+        let gaps = self.get_border_gaps();
+        if !gaps.is_empty() {
+            problems.push(WorldProblem::BrokenBorder { gaps });
+        }
+
+        let player_pos = self.save_manager.get_player_pos();
+        if player_pos
+            .iter()
+            .zip(self.get_size().iter())
+            .any(|(c, size)| !c.is_finite() || *c < 0. || *c >= *size as f32)
+        {
+            problems.push(WorldProblem::PlayerOutsideWorld { pos: player_pos });
+        } else {
+            // Check the feet and the head of the player
+            let feet_pos = get_player_block_pos(player_pos);
+            for block_pos in [feet_pos, feet_pos + Vector3::new(0, 1, 0)] {
+                if let Some(block_type) = self.get_block(block_pos).filter(|b| !b.is_air()) {
+                    problems.push(WorldProblem::PlayerInsideBlock {
+                        block_pos,
+                        block_type,
+                    });
+                }
+            }
+        }
+
+        let inventory = &self.save_manager.player_data.inventory;
+        for (slot, item_stack) in inventory.get_all_slots().iter().enumerate() {
+            let item_type = item_stack.get_item_type();
+            if item_stack.get_amount() > item_type.get_max_stack_amount() {
+                problems.push(WorldProblem::StackTooLarge {
+                    slot,
+                    item_type,
+                    amount: item_stack.get_amount(),
+                });
+            }
+            if item_stack.creative_slot && self.save_manager.get_game_mode() == GameMode::Survival {
+                problems.push(WorldProblem::CreativeSlotInSurvival { slot });
+            }
+        }
+
+        if inventory.get_all_slots().len() != PLAYER_INVENTORY_SIZE {
+            problems.push(WorldProblem::WrongInventorySize {
+                size: inventory.get_all_slots().len(),
+            });
+        }
+
+        let world_name = &self.save_manager.world_info.world_name;
+        if world_name.len() > MAX_WORLD_NAME_SIZE {
+            problems.push(WorldProblem::WorldNameTooLarge {
+                size: world_name.len(),
+            });
+        }
+        if world_name.chars().count() > DISPLAYED_WORLD_NAME_LENGTH {
+            problems.push(WorldProblem::LongWorldName {
+                length: world_name.chars().count(),
+            });
+        }
+        if let Some(character) = world_name.chars().find(|c| !is_displayable_character(*c)) {
+            problems.push(WorldProblem::WorldNameUnprintable { character });
+        }
+
+        problems
+    }
+}
@@ -0,0 +1,133 @@
+use dioxus::prelude::*;
+
+use crate::{
+    deserializer::get_world_info,
+    world_converter::fork::{
+        get_forks, install_fork, parse_fork_description, remove_fork, ForkDescription,
+    },
+    WorldRecord,
+};
+
+/// The loaded forks are kept in the local storage of the browser.
+const FORKS_STORAGE_KEY: &str = "numcraft-forks";
+
+async fn load_stored_forks() -> Vec<ForkDescription> {
+    let mut eval = document::eval(
+        r#"var key = await dioxus.recv();
+            dioxus.send(localStorage.getItem(key) ?? "");"#,
+    );
+    if eval.send(FORKS_STORAGE_KEY).is_err() {
+        return Vec::new();
+    }
+    let value: String = eval.recv().await.unwrap_or_default();
+
+    serde_json::from_str(&value).unwrap_or_default()
+}
+
+fn store_forks(forks: &[ForkDescription]) {
+    let eval = document::eval(
+        r#"var key = await dioxus.recv();
+            var value = await dioxus.recv();
+            try {
+                localStorage.setItem(key, value);
+            } catch (error) {
+                console.log("Can't save the forks: " + error);
+            }"#,
+    );
+    let _ = eval.send(FORKS_STORAGE_KEY);
+    let _ = eval.send(serde_json::to_string(forks).unwrap_or_default());
+}
+
+/// Detect the version of the worlds again after the forks changed.
+fn refresh_world_versions(worlds_list: &mut Signal<Vec<WorldRecord>>) {
+    for record in worlds_list.write().iter_mut() {
+        if let Some(world_info) = get_world_info(&record.world_data) {
+            record.world_info = world_info;
+        }
+    }
+}
+
+#[component]
+pub fn ForkPanel(worlds_list: Signal<Vec<WorldRecord>>) -> Element {
+    let mut forks = use_signal(get_forks);
+    let mut message: Signal<Option<String>> = use_signal(|| None);
+
+    use_future(move || async move {
+        for fork in load_stored_forks().await {
+            install_fork(fork);
+        }
+        forks.set(get_forks());
+        refresh_world_versions(&mut worlds_list);
+    });
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "Custom builds" }
+            span {
+                "Load the description of a fork (JSON or TOML) to open, edit and update the worlds it created."
+            }
+            for fork in forks.read().iter() {
+                div { key: "{fork.world_version}", class: "world-panel-row",
+                    {
+                        format!(
+                            "{}: world version {}, {} blocks, {} items",
+                            fork.name,
+                            fork.world_version,
+                            fork.registry.blocks.len(),
+                            fork.registry.items.len(),
+                        )
+                    }
+                    button {
+                        class: "page-button",
+                        onclick: {
+                            let world_version = fork.world_version;
+                            move |_| {
+                                remove_fork(world_version);
+                                store_forks(&get_forks());
+                                forks.set(get_forks());
+                                refresh_world_versions(&mut worlds_list);
+                            }
+                        },
+                        "Remove"
+                    }
+                }
+            }
+            div { class: "world-panel-row",
+                button {
+                    class: "page-button",
+                    onclick: move |_| {
+                        document::eval("document.getElementById(\"fork_file_picker\").click();");
+                    },
+                    "Load a fork description"
+                }
+                input {
+                    id: "fork_file_picker",
+                    accept: ".json,.toml",
+                    r#type: "file",
+                    hidden: true,
+                    onchange: move |e| async move {
+                        for file in e.files() {
+                            let Ok(bytes) = file.read_bytes().await else {
+                                message.set(Some("The file can't be read.".to_string()));
+                                continue;
+                            };
+                            match parse_fork_description(&String::from_utf8_lossy(&bytes)) {
+                                Ok(fork) => {
+                                    message.set(Some(format!("The fork {} was loaded.", fork.name)));
+                                    install_fork(fork);
+                                }
+                                Err(error) => message.set(Some(error.get_description())),
+                            }
+                        }
+                        store_forks(&get_forks());
+                        forks.set(get_forks());
+                        refresh_world_versions(&mut worlds_list);
+                    },
+                }
+            }
+            if let Some(message) = &*message.read() {
+                span { {message.clone()} }
+            }
+        }
+    )
+}
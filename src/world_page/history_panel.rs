@@ -0,0 +1,117 @@
+use dioxus::prelude::*;
+
+use crate::world_editor::{history::EditHistory, World};
+
+/// The history of a world is kept in the local storage of the browser until the world is saved to the calculator.
+fn get_storage_key(file_name: &str) -> String {
+    format!("numcraft-history-{file_name}")
+}
+
+async fn load_stored_history(key: &str) -> Option<EditHistory> {
+    let mut eval = document::eval(
+        r#"var key = await dioxus.recv();
+            dioxus.send(localStorage.getItem(key) ?? "");"#,
+    );
+    eval.send(key).ok()?;
+    let value: String = eval.recv().await.ok()?;
+
+    EditHistory::from_storage_string(&value)
+}
+
+fn store_history(key: &str, history: &EditHistory) {
+    let eval = document::eval(
+        r#"var key = await dioxus.recv();
+            var value = await dioxus.recv();
+            try {
+                if (value === "") {
+                    localStorage.removeItem(key);
+                } else {
+                    localStorage.setItem(key, value);
+                }
+            } catch (error) {
+                console.log("Can't save the edit history: " + error);
+            }"#,
+    );
+    let value = if history.is_empty() {
+        String::new()
+    } else {
+        history.to_storage_string()
+    };
+    let _ = eval.send(key);
+    let _ = eval.send(value);
+}
+
+#[component]
+pub fn HistoryPanel(world: Signal<World>, file_name: String) -> Element {
+    let key = get_storage_key(&file_name);
+    let mut restored = use_signal(|| false);
+    let mut restore_message: Signal<Option<String>> = use_signal(|| None);
+
+    // Replay the edits made before the page was reloaded
+    let restore_key = key.clone();
+    use_future(move || {
+        let key = restore_key.clone();
+        async move {
+            if let Some(history) = load_stored_history(&key).await {
+                let count = history.get_position();
+                if world.write().restore_history(history) {
+                    restore_message.set(Some(format!("{count} unsaved edits were restored.")));
+                }
+            }
+            restored.set(true);
+        }
+    });
+
+    let store_key = key.clone();
+    use_effect(move || {
+        let world = world.read();
+        if restored() {
+            store_history(&store_key, world.get_history());
+        }
+    });
+
+    let history = world.read().get_history().clone();
+    let position = history.get_position();
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "History" }
+            div { class: "world-panel-row",
+                button {
+                    class: "page-button",
+                    disabled: !history.can_undo(),
+                    onclick: move |_| {
+                        world.write().undo();
+                    },
+                    "Undo"
+                }
+                button {
+                    class: "page-button",
+                    disabled: !history.can_redo(),
+                    onclick: move |_| {
+                        world.write().redo();
+                    },
+                    "Redo"
+                }
+            }
+            if let Some(message) = &*restore_message.read() {
+                span { {message.clone()} }
+            }
+            div { class: "search-results",
+                a {
+                    class: if position == 0 { "search-result history-current" } else { "search-result" },
+                    onclick: move |_| world.write().go_to_history_position(0),
+                    "Loaded world"
+                }
+                for (i, command) in history.get_commands().iter().enumerate() {
+                    a {
+                        key: "{i}",
+                        class: if i + 1 == position { "search-result history-current" } else if i + 1 > position { "search-result history-undone" } else { "search-result" },
+                        onclick: move |_| world.write().go_to_history_position(i + 1),
+                        {command.description.clone()}
+                    }
+                }
+            }
+        }
+    )
+}
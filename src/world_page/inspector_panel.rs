@@ -0,0 +1,154 @@
+use dioxus::prelude::*;
+use numcraft_format::{
+    inspect::{inspect_world, ByteSpace, Inspection, Region},
+    limits::{LimitError, MAX_FILE_SIZE},
+};
+
+use crate::WorldRecord;
+
+/// Longer regions are cut in the hex view.
+const MAX_HEX_LINES: usize = 16;
+
+/// The lines of a hex dump: the offset, 16 bytes in hex and the printable characters.
+fn get_hex_lines(bytes: &[u8], offset: usize) -> Vec<String> {
+    bytes
+        .chunks(16)
+        .take(MAX_HEX_LINES)
+        .enumerate()
+        .map(|(i, line)| {
+            let hex: Vec<String> = line.iter().map(|b| format!("{b:02x}")).collect();
+            let text: String = line
+                .iter()
+                .map(|b| {
+                    if b.is_ascii_graphic() || *b == b' ' {
+                        *b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!("{:06x}  {:<47}  {text}", offset + i * 16, hex.join(" "))
+        })
+        .collect()
+}
+
+fn get_region_title(region: &Region) -> String {
+    let space = match region.space {
+        ByteSpace::File => "file",
+        ByteSpace::Content => "content",
+    };
+    format!(
+        "{space} {:#06x} +{}: {} = {}",
+        region.offset, region.size, region.label, region.value
+    )
+}
+
+#[component]
+fn RegionView(
+    title: String,
+    problem: Option<String>,
+    hex_lines: Vec<String>,
+    hidden_bytes: usize,
+) -> Element {
+    rsx!(
+        div { class: "inspector-region",
+            span { class: if problem.is_some() { "world-panel-error" } else { "" }, {title} }
+            if let Some(problem) = &problem {
+                span { class: "world-panel-error", {problem.clone()} }
+            }
+            if !hex_lines.is_empty() {
+                span { class: "hex-view", {hex_lines.join("\n")} }
+            }
+            if hidden_bytes > 0 {
+                span { {format!("... and {hidden_bytes} more bytes.")} }
+            }
+        }
+    )
+}
+
+#[component]
+pub fn InspectorPanel(worlds_list: Signal<Vec<WorldRecord>>) -> Element {
+    let mut inspection: Signal<Option<(String, Inspection)>> = use_signal(|| None);
+    let mut message: Signal<Option<String>> = use_signal(|| None);
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "Inspector" }
+            span {
+                "Show every part of a world file with its decoded value, to find out why a world doesn't load."
+            }
+            div { class: "world-panel-row",
+                select {
+                    value: "",
+                    onchange: move |e| {
+                        let Ok(index) = e.value().parse::<usize>() else {
+                            return;
+                        };
+                        if let Some(record) = worlds_list.read().get(index) {
+                            message.set(None);
+                            inspection.set(Some((format!("{}.ncw", record.file_name), inspect_world(&record.world_data))));
+                        }
+                    },
+                    option { value: "", "Choose a world" }
+                    for (i, record) in worlds_list.read().iter().enumerate() {
+                        option { value: "{i}", {format!("{}.ncw", record.file_name)} }
+                    }
+                }
+                button {
+                    class: "page-button",
+                    onclick: move |_| {
+                        document::eval("document.getElementById(\"inspect_file_picker\").click();");
+                    },
+                    "Inspect a file"
+                }
+                input {
+                    id: "inspect_file_picker",
+                    accept: ".ncw",
+                    r#type: "file",
+                    hidden: true,
+                    onchange: move |e| async move {
+                        for file in e.files() {
+                            if file.size() > MAX_FILE_SIZE as u64 {
+                                let error = LimitError::FileTooLarge { size: file.size() as usize };
+                                message.set(Some(error.get_description()));
+                                continue;
+                            }
+                            let Ok(bytes) = file.read_bytes().await else {
+                                message.set(Some("The file can't be read.".to_string()));
+                                continue;
+                            };
+                            message.set(None);
+                            inspection.set(Some((file.name(), inspect_world(&bytes))));
+                        }
+                    },
+                }
+            }
+            if let Some(message) = &*message.read() {
+                span { class: "world-panel-error", {message.clone()} }
+            }
+            if let Some((name, inspection)) = &*inspection.read() {
+                span {
+                    {
+                        format!(
+                            "{name}: {} bytes, {} regions, {} problems.",
+                            inspection.file.len(),
+                            inspection.regions.len(),
+                            inspection.get_problems().count(),
+                        )
+                    }
+                }
+                div { class: "search-results",
+                    for (i, region) in inspection.regions.iter().enumerate() {
+                        RegionView {
+                            key: "{i}",
+                            title: get_region_title(region),
+                            problem: region.problem.clone(),
+                            hex_lines: get_hex_lines(inspection.get_bytes(region), region.offset),
+                            hidden_bytes: region.size.saturating_sub(MAX_HEX_LINES * 16),
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
@@ -0,0 +1,142 @@
+use dioxus::prelude::*;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::constants::ItemType,
+    world_editor::{inventory::get_item_types, World},
+    world_page::world_map::get_block_color,
+};
+
+fn get_item_color(item_type: ItemType) -> &'static str {
+    item_type
+        .get_matching_block_type()
+        .map_or("transparent", get_block_color)
+}
+
+fn parse_item_type(value: &str) -> ItemType {
+    value
+        .parse()
+        .ok()
+        .and_then(ItemType::get_from_id)
+        .unwrap_or(ItemType::Air)
+}
+
+#[component]
+pub fn InventoryPanel(world: Signal<World>) -> Element {
+    let mut selected_slot: Signal<Option<usize>> = use_signal(|| None);
+    let mut dragged_slot: Signal<Option<usize>> = use_signal(|| None);
+    let mut given_item = use_signal(|| ItemType::StoneBlock);
+    let mut given_amount = use_signal(|| 1u32);
+    let mut give_message: Signal<Option<String>> = use_signal(|| None);
+
+    let slots = world.read().get_inventory().get_all_slots().clone();
+    let selected_stack = selected_slot().and_then(|slot| slots.get(slot).copied());
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "Inventory" }
+            div { class: "inventory-grid",
+                for (slot, item_stack) in slots.iter().copied().enumerate() {
+                    div {
+                        key: "{slot}",
+                        class: if selected_slot() == Some(slot) { "inventory-slot inventory-slot-selected" } else { "inventory-slot" },
+                        style: "background-color: {get_item_color(item_stack.get_item_type())};",
+                        title: "{item_stack.get_item_type().get_name()}",
+                        draggable: "true",
+                        onclick: move |_| selected_slot.set(Some(slot)),
+                        ondragstart: move |_| dragged_slot.set(Some(slot)),
+                        ondragover: move |e| e.prevent_default(),
+                        ondrop: move |e| {
+                            e.prevent_default();
+                            if let Some(start_slot) = dragged_slot.take() {
+                                world.write().edit_state("Move an item", |world| world.move_inventory_item(start_slot, slot));
+                                selected_slot.set(Some(slot));
+                            }
+                        },
+                        if item_stack.get_item_type() != ItemType::Air {
+                            span { class: "inventory-slot-amount",
+                                if item_stack.creative_slot { "∞" } else { "{item_stack.get_amount()}" }
+                            }
+                        }
+                    }
+                }
+            }
+            if let (Some(slot), Some(item_stack)) = (selected_slot(), selected_stack) {
+                div { class: "world-panel-row",
+                    span { {format!("Slot {}", slot + 1)} }
+                    select {
+                        value: "{item_stack.get_item_type() as u8}",
+                        onchange: move |e| {
+                            let item_type = parse_item_type(&e.value());
+                            let amount = item_stack.get_amount().max(1);
+                            world.write().edit_state("Change a slot", |world| world.set_inventory_slot(slot, item_type, amount));
+                        },
+                        option { value: "0", "Empty" }
+                        for item_type in get_item_types() {
+                            option { value: "{item_type as u8}", "{item_type.get_name()}" }
+                        }
+                    }
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        max: "{item_stack.get_item_type().get_max_stack_amount()}",
+                        disabled: item_stack.creative_slot || item_stack.get_item_type() == ItemType::Air,
+                        value: "{item_stack.get_amount()}",
+                        onchange: move |e| {
+                            if let Ok(amount) = e.value().parse::<u32>() {
+                                let amount = amount.min(u8::MAX as u32) as u8;
+                                world.write().edit_state("Change an amount", |world| world.set_inventory_slot_amount(slot, amount));
+                            }
+                        },
+                    }
+                }
+            }
+            div { class: "world-panel-row",
+                button {
+                    class: "page-button",
+                    onclick: move |_| world.write().edit_state("Sort the inventory", |world| world.sort_inventory()),
+                    "Sort"
+                }
+                button {
+                    class: "page-button",
+                    onclick: move |_| world.write().edit_state("Merge the stacks", |world| world.merge_inventory_stacks()),
+                    "Merge stacks"
+                }
+            }
+            div { class: "world-panel-row",
+                select {
+                    value: "{given_item() as u8}",
+                    onchange: move |e| given_item.set(parse_item_type(&e.value())),
+                    for item_type in get_item_types() {
+                        option { value: "{item_type as u8}", "{item_type.get_name()}" }
+                    }
+                }
+                input {
+                    r#type: "number",
+                    min: "1",
+                    value: "{given_amount}",
+                    onchange: move |e| {
+                        if let Ok(amount) = e.value().parse() {
+                            given_amount.set(amount);
+                        }
+                    },
+                }
+                button {
+                    class: "page-button",
+                    onclick: move |_| {
+                        let (item_type, amount) = (given_item(), given_amount());
+                        let remaining = world.write().edit_state(&format!("Give {amount} {item_type:?}"), |world| world.give_item(item_type, amount));
+                        give_message.set(if remaining > 0 {
+                            Some(format!("The inventory is full, {remaining} items were not given."))
+                        } else {
+                            None
+                        });
+                    },
+                    "Give"
+                }
+            }
+            if let Some(message) = &*give_message.read() {
+                span { {message.clone()} }
+            }
+        }
+    )
+}
@@ -0,0 +1,216 @@
+use dioxus::prelude::*;
+use nalgebra::Vector3;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::constants::BlockType, world_editor::World,
+    world_page::world_map::get_block_color,
+};
+
+#[derive(Clone, Copy, PartialEq)]
+enum PaintTool {
+    Pencil,
+    Line,
+    Rectangle,
+    Fill,
+}
+
+impl PaintTool {
+    const ALL: [PaintTool; 4] = [
+        PaintTool::Pencil,
+        PaintTool::Line,
+        PaintTool::Rectangle,
+        PaintTool::Fill,
+    ];
+
+    fn get_name(&self) -> &'static str {
+        match self {
+            PaintTool::Pencil => "Pencil",
+            PaintTool::Line => "Line",
+            PaintTool::Rectangle => "Rectangle",
+            PaintTool::Fill => "Fill",
+        }
+    }
+
+    /// Tools that need a second click to be applied
+    fn needs_anchor(&self) -> bool {
+        matches!(self, PaintTool::Line | PaintTool::Rectangle)
+    }
+}
+
+/// Run an edit through `World::edit` and describe what happened.
+fn run_edit(
+    world: &mut Signal<World>,
+    description: &str,
+    edit: impl FnOnce(&mut World) -> usize,
+) -> Option<String> {
+    match world.write().edit(description, edit) {
+        Ok((changed, report)) => {
+            let mut details = Vec::new();
+            if let Some(survival) = &report.survival {
+                details.push(survival.get_description());
+            }
+            if !report.border.replaced.is_empty() {
+                details.push(report.border.get_description());
+            }
+
+            if changed == 0 && details.is_empty() {
+                None
+            } else {
+                Some(format!("{changed} blocks changed. {}", details.join(" ")))
+            }
+        }
+        Err(error) => Some(error.get_description()),
+    }
+}
+
+/// Shows one horizontal layer of the world and lets the user paint blocks in it.
+#[component]
+pub fn LayerEditor(world: Signal<World>) -> Element {
+    let size = world.read().get_size();
+
+    let mut layer = use_signal(|| 1isize);
+    let mut block_type = use_signal(|| BlockType::Stone);
+    let mut tool = use_signal(|| PaintTool::Pencil);
+    let mut anchor: Signal<Option<Vector3<isize>>> = use_signal(|| None);
+    // The cells of the pencil stroke being drawn. They are painted together when the mouse is released, so the
+    // stroke is a single command of the history
+    let mut stroke: Signal<Option<Vec<Vector3<isize>>>> = use_signal(|| None);
+    let mut message: Signal<Option<String>> = use_signal(|| None);
+
+    let y = layer().clamp(0, size.y - 1);
+
+    let mut click_cell = move |pos: Vector3<isize>| {
+        let block_type = block_type();
+        let result = match tool() {
+            PaintTool::Pencil => {
+                stroke.set(Some(vec![pos]));
+                return;
+            }
+            PaintTool::Fill => run_edit(&mut world, "Fill", |world| {
+                world.flood_fill_layer(pos, block_type)
+            }),
+            PaintTool::Line | PaintTool::Rectangle => match anchor.take() {
+                Some(from) if tool() == PaintTool::Line => run_edit(&mut world, "Line", |world| {
+                    world.paint_line(from, pos, block_type)
+                }),
+                Some(from) => run_edit(&mut world, "Rectangle", |world| {
+                    world.paint_rectangle(from, pos, block_type)
+                }),
+                None => {
+                    anchor.set(Some(pos));
+                    return;
+                }
+            },
+        };
+        if result.is_some() {
+            message.set(result);
+        }
+    };
+
+    let mut finish_stroke = move || {
+        let Some(cells) = stroke.take() else {
+            return;
+        };
+        let block_type = block_type();
+        let result = run_edit(&mut world, "Pencil", |world| {
+            cells
+                .iter()
+                .filter(|pos| world.set_block(**pos, block_type))
+                .count()
+        });
+        if result.is_some() {
+            message.set(result);
+        }
+    };
+
+    rsx!(
+        div { class: "world-panel layer-editor",
+            span { class: "world-panel-title", "Layer editor" }
+            div { class: "world-panel-row",
+                for paint_tool in PaintTool::ALL {
+                    button {
+                        class: if tool() == paint_tool { "page-button page-button-selected" } else { "page-button" },
+                        onclick: move |_| {
+                            tool.set(paint_tool);
+                            anchor.set(None);
+                        },
+                        {paint_tool.get_name()}
+                    }
+                }
+                select {
+                    value: "{block_type() as u8}",
+                    onchange: move |e| {
+                        if let Some(new_block_type) = e.value().parse().ok().and_then(BlockType::get_from_id) {
+                            block_type.set(new_block_type);
+                        }
+                    },
+                    for paint_block_type in (0..=u8::MAX).filter_map(BlockType::get_from_id) {
+                        option { value: "{paint_block_type as u8}", "{paint_block_type.get_name()}" }
+                    }
+                }
+            }
+            div { class: "world-panel-row",
+                {format!("Layer y: {y}")}
+                input {
+                    r#type: "range",
+                    min: "0",
+                    max: "{size.y - 1}",
+                    value: "{y}",
+                    oninput: move |e| {
+                        if let Ok(new_layer) = e.value().parse() {
+                            layer.set(new_layer);
+                            anchor.set(None);
+                        }
+                    },
+                }
+            }
+            if tool().needs_anchor() {
+                span {
+                    if anchor().is_some() { "Click the second corner." } else { "Click the first corner." }
+                }
+            }
+            div {
+                class: "layer-grid",
+                style: "grid-template-columns: repeat({size.x}, 4mm);",
+                onmouseup: move |_| finish_stroke(),
+                onmouseleave: move |_| finish_stroke(),
+                for z in 0..size.z {
+                    for x in 0..size.x {
+                        {
+                            let pos = Vector3::new(x, y, z);
+                            let cell_block_type = if stroke.read().as_ref().is_some_and(|cells| cells.contains(&pos)) {
+                                block_type()
+                            } else {
+                                world.read().get_block(pos).unwrap_or(BlockType::Air)
+                            };
+                            rsx!(
+                                div {
+                                    key: "{x}-{z}",
+                                    class: "layer-grid-cell",
+                                    class: if anchor() == Some(pos) { "world-map-cell-highlighted" } else { "" },
+                                    style: "background-color: {get_block_color(cell_block_type)};",
+                                    title: "x: {x}, z: {z}, {cell_block_type.get_name()}",
+                                    onmousedown: move |e| {
+                                        e.prevent_default();
+                                        click_cell(pos);
+                                    },
+                                    onmouseenter: move |_| {
+                                        let new_cell = stroke.read().as_ref().is_some_and(|cells| !cells.contains(&pos));
+                                        if new_cell {
+                                            if let Some(cells) = stroke.write().as_mut() {
+                                                cells.push(pos);
+                                            }
+                                        }
+                                    },
+                                }
+                            )
+                        }
+                    }
+                }
+            }
+            if let Some(message) = &*message.read() {
+                span { {message.clone()} }
+            }
+        }
+    )
+}
@@ -0,0 +1,102 @@
+use dioxus::prelude::*;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::save_manager::GameMode,
+    world_editor::{metadata::check_world_name, World},
+};
+
+#[component]
+pub fn MetadataPanel(world: Signal<World>) -> Element {
+    let mut name = use_signal(|| world.peek().save_manager.world_info.world_name.clone());
+    let mut seed = use_signal(|| world.peek().save_manager.world_info.world_seed.to_string());
+    let mut convert_inventory = use_signal(|| true);
+    let mut message: Signal<Option<String>> = use_signal(|| None);
+
+    let name_check = check_world_name(&name.read());
+    let parsed_seed = seed.read().trim().parse::<i32>().ok();
+    let game_mode = world.read().save_manager.get_game_mode();
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "World" }
+            div { class: "world-panel-row",
+                "Name:"
+                input {
+                    value: "{name}",
+                    oninput: move |e| name.set(e.value()),
+                }
+                button {
+                    class: "page-button",
+                    disabled: name_check.is_err(),
+                    onclick: move |_| {
+                        let new_name = name.read().clone();
+                        if world.write().edit_state("Rename the world", |world| world.rename(&new_name)).is_ok() {
+                            message.set(Some("The world was renamed.".to_string()));
+                        }
+                    },
+                    "Rename"
+                }
+            }
+            if let Err(error) = name_check {
+                span { class: "world-panel-error", {error.get_description()} }
+            }
+            div { class: "world-panel-row",
+                "Seed:"
+                input {
+                    r#type: "number",
+                    value: "{seed}",
+                    oninput: move |e| seed.set(e.value()),
+                }
+                button {
+                    class: "page-button",
+                    disabled: parsed_seed.is_none(),
+                    onclick: move |_| {
+                        if let Some(new_seed) = parsed_seed {
+                            world.write().edit_state("Change the seed", |world| world.set_seed(new_seed));
+                            message.set(Some(format!("The seed is now {new_seed}.")));
+                        }
+                    },
+                    "Set the seed"
+                }
+            }
+            div { class: "world-panel-row",
+                "Gamemode:"
+                select {
+                    value: if game_mode == GameMode::Creative { "creative" } else { "survival" },
+                    onchange: move |e| {
+                        let new_game_mode = if e.value() == "creative" { GameMode::Creative } else { GameMode::Survival };
+                        let convert = convert_inventory();
+                        world.write().edit_state("Change the gamemode", |world| world.set_game_mode(new_game_mode, convert));
+                        message.set(Some(if convert_inventory() {
+                            "The gamemode was changed and the inventory was converted.".to_string()
+                        } else {
+                            "The gamemode was changed.".to_string()
+                        }));
+                    },
+                    option { value: "survival", "Survival" }
+                    option { value: "creative", "Creative" }
+                }
+            }
+            label { class: "world-panel-row",
+                input {
+                    r#type: "checkbox",
+                    checked: convert_inventory(),
+                    onchange: move |e| convert_inventory.set(e.checked()),
+                }
+                "Convert the inventory when switching gamemode"
+            }
+            label { class: "world-panel-row",
+                input {
+                    r#type: "checkbox",
+                    disabled: game_mode == GameMode::Creative,
+                    checked: world.read().survival_fair,
+                    onchange: move |e| world.write().survival_fair = e.checked(),
+                }
+                "Survival-fair editing: mined blocks go to the inventory and placed blocks cost items"
+            }
+            if let Some(message) = &*message.read() {
+                span { {message.clone()} }
+            }
+        }
+    )
+}
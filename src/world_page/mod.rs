@@ -0,0 +1,138 @@
+/* The page used to inspect and edit a single v0.1.3 world */
+
+use dioxus::{logger::tracing, prelude::*};
+use nalgebra::Vector3;
+
+use crate::{
+    install_world_data, update_worlds_list,
+    world_converter::fork::{convert_to_fork, get_fork},
+    world_editor::{history::get_checksum, World},
+    WorldRecord,
+};
+
+mod fork_panel;
+mod history_panel;
+mod inspector_panel;
+mod inventory_panel;
+mod layer_editor;
+mod metadata_panel;
+mod optimize_panel;
+mod player_panel;
+mod sculpt_panel;
+mod script_panel;
+mod search_panel;
+mod statistics_panel;
+mod validation_panel;
+mod world_map;
+
+use history_panel::HistoryPanel;
+pub use inspector_panel::InspectorPanel;
+use inventory_panel::InventoryPanel;
+use layer_editor::LayerEditor;
+use metadata_panel::MetadataPanel;
+pub use optimize_panel::OptimizePanel;
+use player_panel::PlayerPanel;
+use sculpt_panel::SculptPanel;
+use script_panel::ScriptPanel;
+pub use fork_panel::ForkPanel;
+pub use script_panel::BatchScriptPanel;
+use search_panel::SearchPanel;
+use statistics_panel::StatisticsPanel;
+use validation_panel::ValidationPanel;
+use world_map::WorldMap;
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct OpenedWorld {
+    pub world_index: usize,
+    pub world: Signal<World>,
+    /// The world version of the fork the world comes from. The world is edited with the v0.1.3 ids.
+    pub fork_version: Option<u16>,
+}
+
+impl OpenedWorld {
+    /// The world signal is owned by the app so it survives the worlds list page being unmounted.
+    pub fn new(world_index: usize, world: World, fork_version: Option<u16>) -> Self {
+        OpenedWorld {
+            world_index,
+            world: Signal::new_in_scope(world, ScopeId::APP),
+            fork_version,
+        }
+    }
+
+    pub fn close(&self, opened_world: &mut Signal<Option<OpenedWorld>>) {
+        opened_world.set(None);
+        self.world.manually_drop();
+    }
+}
+
+#[component]
+pub fn WorldPage(
+    calculator_connected: Signal<bool>,
+    worlds_list: Signal<Vec<WorldRecord>>,
+    opened_world: Signal<Option<OpenedWorld>>,
+    opened: OpenedWorld,
+) -> Element {
+    let highlighted: Signal<Vec<Vector3<isize>>> = use_signal(Vec::new);
+    let picked_column: Signal<Option<(isize, isize)>> = use_signal(|| None);
+    let mut saving = use_signal(|| false);
+
+    rsx!(
+        div { id: "world-page-div", class: "page-spawn-fade-down",
+            div { id: "world-page-header",
+                button {
+                    class: "page-button",
+                    onclick: move |_| opened.close(&mut opened_world),
+                    "Back"
+                }
+                span { class: "connection-info",
+                    {format!("World: {}", opened.world.read().save_manager.world_info.world_name)}
+                }
+                if let Some(fork) = opened.fork_version.and_then(get_fork) {
+                    span { class: "connection-info", {format!("Fork: {}", fork.name)} }
+                }
+                button {
+                    class: "page-button",
+                    disabled: *saving.read(),
+                    onclick: move |_| async move {
+                        saving.set(true);
+                        let data = opened.world.write().get_raw();
+                        let checksum = get_checksum(&data);
+                        let record_index = worlds_list.read()[opened.world_index].record_index;
+                        let data = match opened.fork_version {
+                            Some(fork_version) => get_fork(fork_version).and_then(|fork| {
+                                convert_to_fork(&data, Some(&worlds_list.read()[opened.world_index].world_data), &fork)
+                            }),
+                            None => Some(data),
+                        };
+                        match data {
+                            Some(data) => {
+                                install_world_data(record_index, data).await;
+                                opened.world.write().reset_history(checksum);
+                                update_worlds_list(&mut calculator_connected, &mut worlds_list, false).await;
+                            }
+                            None => tracing::warn!("The world can't be converted back to its fork."),
+                        }
+                        saving.set(false);
+                    },
+                    if *saving.read() { "Saving..." } else { "Save to the calculator" }
+                }
+            }
+            div { id: "world-page-body",
+                WorldMap { world: opened.world, highlighted, picked_column }
+                LayerEditor { world: opened.world }
+                HistoryPanel {
+                    world: opened.world,
+                    file_name: worlds_list.read()[opened.world_index].file_name.clone(),
+                }
+                SculptPanel { world: opened.world, highlighted, picked_column }
+                ScriptPanel { world: opened.world }
+                SearchPanel { world: opened.world, highlighted }
+                MetadataPanel { world: opened.world }
+                PlayerPanel { world: opened.world, highlighted, picked_column }
+                InventoryPanel { world: opened.world }
+                StatisticsPanel { world: opened.world }
+                ValidationPanel { world: opened.world, highlighted }
+            }
+        }
+    )
+}
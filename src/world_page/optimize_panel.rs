@@ -0,0 +1,94 @@
+use dioxus::prelude::*;
+use numcraft_format::format::save_manager::optimize_world;
+
+use crate::{install_world_data, update_worlds_list, WorldRecord};
+
+fn format_saving(saved: usize, size: usize) -> String {
+    if size == 0 {
+        return format!("{saved} bytes saved");
+    }
+    format!(
+        "{saved} bytes saved ({:.1}%)",
+        saved as f32 / size as f32 * 100.
+    )
+}
+
+#[component]
+pub fn OptimizePanel(
+    calculator_connected: Signal<bool>,
+    worlds_list: Signal<Vec<WorldRecord>>,
+) -> Element {
+    let mut dry_run = use_signal(|| true);
+    let mut running = use_signal(|| false);
+    let mut output: Signal<Vec<(String, bool)>> = use_signal(Vec::new);
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "Optimize the storage" }
+            span {
+                "Compress the worlds again with a slower encoder that finds a smaller encoding. The game reads them as before and nothing else changes in the worlds."
+            }
+            div { class: "world-panel-row",
+                label { class: "world-panel-row",
+                    input {
+                        r#type: "checkbox",
+                        checked: dry_run(),
+                        onchange: move |e| dry_run.set(e.checked()),
+                    }
+                    "Dry run"
+                }
+                button {
+                    class: "page-button",
+                    disabled: running(),
+                    onclick: move |_| async move {
+                        running.set(true);
+                        output.set(Vec::new());
+
+                        let records: Vec<WorldRecord> = worlds_list.read().clone();
+                        let mut total_size = 0;
+                        let mut total_saved = 0;
+
+                        for record in records {
+                            let file_name = &record.file_name;
+                            let Some(dimensions) = record.world_info.world_version.get_dimensions() else {
+                                output.write().push((format!("{file_name}.ncw: skipped, the site doesn't know the format of this version."), true));
+                                continue;
+                            };
+                            let Some(optimized) = optimize_world(&record.world_data, dimensions) else {
+                                output.write().push((format!("{file_name}.ncw: skipped, the world can't be read."), true));
+                                continue;
+                            };
+
+                            let size = record.world_data.len();
+                            let saved = size - optimized.len();
+                            total_size += size;
+                            total_saved += saved;
+                            output.write().push((format!("{file_name}.ncw: {size} -> {} bytes, {}.", optimized.len(), format_saving(saved, size)), false));
+
+                            if !dry_run() && saved > 0 {
+                                install_world_data(record.record_index, optimized).await;
+                            }
+                        }
+
+                        output.write().push((format!("Total: {total_size} -> {} bytes, {}.", total_size - total_saved, format_saving(total_saved, total_size)), false));
+
+                        if !dry_run() {
+                            update_worlds_list(&mut calculator_connected, &mut worlds_list, false).await;
+                        }
+                        running.set(false);
+                    },
+                    if running() { "Optimizing..." } else { "Optimize all the worlds" }
+                }
+            }
+            div { class: "search-results",
+                for (i, (line, failed)) in output.read().iter().enumerate() {
+                    span {
+                        key: "{i}",
+                        class: if *failed { "world-panel-error" } else { "" },
+                        {line.clone()}
+                    }
+                }
+            }
+        }
+    )
+}
@@ -0,0 +1,212 @@
+use dioxus::prelude::*;
+use nalgebra::Vector3;
+
+use crate::world_editor::{spawn::get_standing_position, validate::get_player_block_pos, World};
+
+fn format_coordinate(value: f32) -> String {
+    format!("{value:.1}")
+}
+
+/// Parse the three coordinates of the teleport form.
+fn parse_position(x: &str, y: &str, z: &str) -> Option<Vector3<f32>> {
+    Some(Vector3::new(
+        x.trim().parse().ok()?,
+        y.trim().parse().ok()?,
+        z.trim().parse().ok()?,
+    ))
+}
+
+/// A short description of where the player will end up if teleported to `pos`.
+fn get_spawn_preview(world: &World, pos: Vector3<f32>) -> String {
+    if let Err(error) = world.check_player_position(pos) {
+        return error.get_description();
+    }
+
+    let feet_pos = get_player_block_pos(pos);
+    match world.get_landing_position(pos) {
+        Some(landing) if landing == feet_pos => {
+            let ground = world.get_block(landing - Vector3::new(0, 1, 0)).unwrap();
+            format!("The player will stand on a {ground:?} block.")
+        }
+        Some(landing) => {
+            let ground = world.get_block(landing - Vector3::new(0, 1, 0)).unwrap();
+            format!(
+                "The player will fall {} blocks and land on a {ground:?} block.",
+                feet_pos.y - landing.y
+            )
+        }
+        None => "There is no ground below this position.".to_string(),
+    }
+}
+
+#[component]
+pub fn PlayerPanel(
+    world: Signal<World>,
+    highlighted: Signal<Vec<Vector3<isize>>>,
+    picked_column: Signal<Option<(isize, isize)>>,
+) -> Element {
+    let mut spawn_message: Signal<Option<String>> = use_signal(|| None);
+
+    let player_pos = world.read().save_manager.get_player_pos();
+    let player_rot = world.read().save_manager.get_player_rot();
+
+    let mut target_x = use_signal(|| format_coordinate(player_pos.x));
+    let mut target_y = use_signal(|| format_coordinate(player_pos.y));
+    let mut target_z = use_signal(|| format_coordinate(player_pos.z));
+    let mut pitch = use_signal(|| format!("{:.0}", player_rot.x.to_degrees()));
+    let mut yaw = use_signal(|| format!("{:.0}", player_rot.y.to_degrees()));
+
+    let landmarks = use_memo(move || world.read().get_landmarks());
+
+    // Fill the form with the surface of the column picked on the map
+    use_effect(move || {
+        if let Some((x, z)) = picked_column() {
+            let feet_pos = world
+                .peek()
+                .get_surface_position(x, z)
+                .unwrap_or(Vector3::new(x, world.peek().get_size().y - 1, z));
+            let pos = get_standing_position(feet_pos);
+            target_x.set(format_coordinate(pos.x));
+            target_y.set(format_coordinate(pos.y));
+            target_z.set(format_coordinate(pos.z));
+        }
+    });
+
+    let target = parse_position(&target_x.read(), &target_y.read(), &target_z.read());
+    let preview = match target {
+        Some(pos) => get_spawn_preview(&world.read(), pos),
+        None => "Enter a valid position.".to_string(),
+    };
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "Player" }
+            span {
+                {format!("Position: x: {:.1}, y: {:.1}, z: {:.1}", player_pos.x, player_pos.y, player_pos.z)}
+            }
+            span {
+                {format!("Rotation: pitch: {:.0}°, yaw: {:.0}°", player_rot.x.to_degrees(), player_rot.y.to_degrees())}
+            }
+            div { class: "world-panel-row",
+                button {
+                    class: "page-button",
+                    onclick: move |_| highlighted.set(vec![get_player_block_pos(player_pos)]),
+                    "Show on the map"
+                }
+                button {
+                    class: "page-button",
+                    onclick: move |_| {
+                        let moved_to = world.write().edit_state("Move to a safe spawn", |world| world.move_player_to_safe_spawn());
+                        let message = match moved_to {
+                            Some(pos) => {
+                                highlighted.set(vec![get_player_block_pos(pos)]);
+                                format!("The player was moved to x: {:.1}, y: {:.1}, z: {:.1}.", pos.x, pos.y, pos.z)
+                            }
+                            None if world.read().find_safe_spawn(player_pos).is_none() => {
+                                "There is no safe place to stand in this world.".to_string()
+                            }
+                            None => "The player is already standing safely.".to_string(),
+                        };
+                        spawn_message.set(Some(message));
+                    },
+                    "Move to a safe spawn"
+                }
+            }
+            span { class: "world-panel-title", "Teleport" }
+            span { "Enter a position, pick a column on the map or choose a landmark." }
+            div { class: "world-panel-row",
+                "x:"
+                input {
+                    class: "coordinate-input",
+                    value: "{target_x}",
+                    oninput: move |e| target_x.set(e.value()),
+                }
+                "y:"
+                input {
+                    class: "coordinate-input",
+                    value: "{target_y}",
+                    oninput: move |e| target_y.set(e.value()),
+                }
+                "z:"
+                input {
+                    class: "coordinate-input",
+                    value: "{target_z}",
+                    oninput: move |e| target_z.set(e.value()),
+                }
+            }
+            div { class: "world-panel-row",
+                select {
+                    onchange: move |e| {
+                        let landmark = e.value().parse::<usize>().ok().and_then(|i| landmarks.read().get(i).cloned());
+                        if let Some(landmark) = landmark {
+                            let pos = get_standing_position(landmark.feet_pos);
+                            target_x.set(format_coordinate(pos.x));
+                            target_y.set(format_coordinate(pos.y));
+                            target_z.set(format_coordinate(pos.z));
+                        }
+                    },
+                    option { value: "", "Landmarks" }
+                    for (i, landmark) in landmarks.read().iter().enumerate() {
+                        option { value: "{i}", {landmark.name.clone()} }
+                    }
+                }
+                button {
+                    class: "page-button",
+                    disabled: target.is_none(),
+                    onclick: move |_| {
+                        if let Some(pos) = target {
+                            highlighted.set(vec![get_player_block_pos(pos)]);
+                        }
+                    },
+                    "Preview on the map"
+                }
+                button {
+                    class: "page-button",
+                    disabled: target.is_none_or(|pos| world.read().check_player_position(pos).is_err()),
+                    onclick: move |_| {
+                        if let Some(pos) = target {
+                            let result = world.write().edit_state("Teleport", |world| world.teleport_player(pos));
+                            spawn_message.set(Some(match result {
+                                Ok(()) => format!("The player was teleported to x: {:.1}, y: {:.1}, z: {:.1}.", pos.x, pos.y, pos.z),
+                                Err(error) => error.get_description(),
+                            }));
+                        }
+                    },
+                    "Teleport"
+                }
+            }
+            span { {preview} }
+            span { class: "world-panel-title", "View direction" }
+            div { class: "world-panel-row",
+                "Pitch:"
+                input {
+                    class: "coordinate-input",
+                    r#type: "number",
+                    min: "-90",
+                    max: "90",
+                    value: "{pitch}",
+                    oninput: move |e| pitch.set(e.value()),
+                }
+                "Yaw:"
+                input {
+                    class: "coordinate-input",
+                    r#type: "number",
+                    value: "{yaw}",
+                    oninput: move |e| yaw.set(e.value()),
+                }
+                button {
+                    class: "page-button",
+                    onclick: move |_| {
+                        if let (Ok(pitch), Ok(yaw)) = (pitch.read().parse::<f32>(), yaw.read().parse::<f32>()) {
+                            world.write().edit_state("Change the rotation", |world| world.set_player_rotation(pitch.to_radians(), yaw.to_radians()));
+                        }
+                    },
+                    "Set the rotation"
+                }
+            }
+            if let Some(message) = &*spawn_message.read() {
+                span { {message.clone()} }
+            }
+        }
+    )
+}
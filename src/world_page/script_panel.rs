@@ -0,0 +1,267 @@
+use dioxus::prelude::*;
+
+use crate::{
+    install_world_data, update_worlds_list,
+    world_editor::{
+        script::{parse_script, ScriptEffect, EXAMPLE_SCRIPTS},
+        World,
+    },
+    WorldRecord,
+};
+
+/// The scripts written on the site are kept in the local storage of the browser, one key per script.
+const SCRIPT_KEY_PREFIX: &str = "numcraft-script-";
+
+async fn load_stored_scripts() -> Vec<(String, String)> {
+    let mut eval = document::eval(
+        r#"var prefix = await dioxus.recv();
+            var scripts = [];
+            for (var i = 0; i < localStorage.length; i++) {
+                var key = localStorage.key(i);
+                if (key.startsWith(prefix)) {
+                    scripts.push([key.slice(prefix.length), localStorage.getItem(key)]);
+                }
+            }
+            dioxus.send(scripts);"#,
+    );
+    if eval.send(SCRIPT_KEY_PREFIX).is_err() {
+        return Vec::new();
+    }
+    let mut scripts: Vec<(String, String)> = eval.recv().await.unwrap_or_default();
+    scripts.sort();
+    scripts
+}
+
+/// An empty source removes the script.
+fn store_script(name: &str, source: &str) {
+    let eval = document::eval(
+        r#"var key = await dioxus.recv();
+            var value = await dioxus.recv();
+            try {
+                if (value === "") {
+                    localStorage.removeItem(key);
+                } else {
+                    localStorage.setItem(key, value);
+                }
+            } catch (error) {
+                console.log("Can't save the script: " + error);
+            }"#,
+    );
+    let _ = eval.send(format!("{SCRIPT_KEY_PREFIX}{name}"));
+    let _ = eval.send(source);
+}
+
+fn format_effect(effect: &ScriptEffect) -> String {
+    format!(
+        "Line {}: {}: {}",
+        effect.line, effect.description, effect.result
+    )
+}
+
+/// Pick, write and save a script. The parse errors are shown while typing.
+#[component]
+fn ScriptEditor(source: Signal<String>) -> Element {
+    let mut name = use_signal(String::new);
+    let mut stored_scripts = use_resource(load_stored_scripts);
+
+    let parse_error = match parse_script(&source.read()) {
+        Ok(_) => None,
+        Err(error) => Some(error.get_description()),
+    };
+
+    rsx!(
+        div { class: "world-panel-row",
+            select {
+                onchange: move |e| {
+                    let value = e.value();
+                    if let Some(i) = value.strip_prefix("example-").and_then(|i| i.parse::<usize>().ok()) {
+                        let (example_name, example_source) = EXAMPLE_SCRIPTS[i];
+                        name.set(example_name.to_string());
+                        source.set(example_source.to_string());
+                    } else if let Some(stored_name) = value.strip_prefix("stored-") {
+                        let stored = stored_scripts
+                            .read()
+                            .iter()
+                            .flatten()
+                            .find(|(n, _)| n == stored_name)
+                            .cloned();
+                        if let Some((stored_name, stored_source)) = stored {
+                            name.set(stored_name);
+                            source.set(stored_source);
+                        }
+                    }
+                },
+                option { value: "", "Scripts" }
+                for (i, (example_name, _)) in EXAMPLE_SCRIPTS.iter().enumerate() {
+                    option { value: "example-{i}", {format!("Example: {example_name}")} }
+                }
+                for (stored_name, _) in stored_scripts.read().iter().flatten() {
+                    option { value: "stored-{stored_name}", {stored_name.clone()} }
+                }
+            }
+            input {
+                placeholder: "Script name",
+                value: "{name}",
+                oninput: move |e| name.set(e.value()),
+            }
+            button {
+                class: "page-button",
+                disabled: name.read().trim().is_empty() || source.read().is_empty(),
+                onclick: move |_| {
+                    store_script(name.read().trim(), &source.read());
+                    stored_scripts.restart();
+                },
+                "Save"
+            }
+            button {
+                class: "page-button",
+                disabled: name.read().trim().is_empty(),
+                onclick: move |_| {
+                    store_script(name.read().trim(), "");
+                    stored_scripts.restart();
+                },
+                "Delete"
+            }
+        }
+        textarea {
+            class: "script-editor",
+            rows: "6",
+            spellcheck: "false",
+            placeholder: "replace Sand with Stone below y=10, give 64 Planks, reset gamemode",
+            value: "{source}",
+            oninput: move |e| source.set(e.value()),
+        }
+        if let Some(error) = parse_error {
+            span { class: "world-panel-error", {error} }
+        }
+    )
+}
+
+/// Run a script on the opened world. Every command of the script can be undone.
+#[component]
+pub fn ScriptPanel(world: Signal<World>) -> Element {
+    let source = use_signal(String::new);
+    let mut dry_run = use_signal(|| true);
+    let mut effects: Signal<Vec<ScriptEffect>> = use_signal(Vec::new);
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "Script" }
+            ScriptEditor { source }
+            div { class: "world-panel-row",
+                label { class: "world-panel-row",
+                    input {
+                        r#type: "checkbox",
+                        checked: dry_run(),
+                        onchange: move |e| dry_run.set(e.checked()),
+                    }
+                    "Dry run"
+                }
+                button {
+                    class: "page-button",
+                    disabled: parse_script(&source.read()).is_err(),
+                    onclick: move |_| {
+                        if let Ok(script) = parse_script(&source.read()) {
+                            effects.set(world.write().run_script(&script, dry_run()));
+                        }
+                    },
+                    "Run"
+                }
+            }
+            div { class: "search-results",
+                for (i, effect) in effects.read().iter().enumerate() {
+                    span {
+                        key: "{i}",
+                        class: if effect.failed { "world-panel-error" } else { "" },
+                        {format_effect(effect)}
+                    }
+                }
+            }
+        }
+    )
+}
+
+/// Run a script on every selected world of the calculator, then send the edited worlds back.
+#[component]
+pub fn BatchScriptPanel(
+    calculator_connected: Signal<bool>,
+    worlds_list: Signal<Vec<WorldRecord>>,
+    selected_worlds: Signal<Vec<usize>>,
+) -> Element {
+    let source = use_signal(String::new);
+    let mut dry_run = use_signal(|| true);
+    let mut running = use_signal(|| false);
+    let mut output: Signal<Vec<(String, bool)>> = use_signal(Vec::new);
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "Batch script" }
+            span { "Check the worlds to edit in the list, then run a script on all of them." }
+            ScriptEditor { source }
+            div { class: "world-panel-row",
+                label { class: "world-panel-row",
+                    input {
+                        r#type: "checkbox",
+                        checked: dry_run(),
+                        onchange: move |e| dry_run.set(e.checked()),
+                    }
+                    "Dry run"
+                }
+                button {
+                    class: "page-button",
+                    disabled: running() || selected_worlds.read().is_empty() || parse_script(&source.read()).is_err(),
+                    onclick: move |_| async move {
+                        let Ok(script) = parse_script(&source.read()) else {
+                            return;
+                        };
+                        running.set(true);
+                        output.set(Vec::new());
+
+                        let records: Vec<(usize, String, Vec<u8>)> = selected_worlds
+                            .read()
+                            .iter()
+                            .filter_map(|i| worlds_list.read().get(*i).map(|r| (r.record_index, r.file_name.clone(), r.world_data.clone())))
+                            .collect();
+
+                        for (record_index, file_name, data) in records {
+                            let mut world = match World::load(&data) {
+                                Ok(world) => world,
+                                Err(error) => {
+                                    output.write().push((format!("{file_name}.ncw: the world can't be loaded. {}", error.get_description()), true));
+                                    continue;
+                                }
+                            };
+
+                            let effects = world.run_script(&script, dry_run());
+                            let failures = effects.iter().filter(|e| e.failed).count();
+                            output.write().push((format!("{file_name}.ncw: {} commands, {failures} failed.", effects.len()), failures > 0));
+                            for effect in &effects {
+                                output.write().push((format_effect(effect), effect.failed));
+                            }
+
+                            if !dry_run() {
+                                install_world_data(record_index, world.get_raw()).await;
+                            }
+                        }
+
+                        if !dry_run() {
+                            selected_worlds.set(Vec::new());
+                            update_worlds_list(&mut calculator_connected, &mut worlds_list, false).await;
+                        }
+                        running.set(false);
+                    },
+                    if running() { "Running..." } else { "Run on the selected worlds" }
+                }
+            }
+            div { class: "search-results",
+                for (i, (line, failed)) in output.read().iter().enumerate() {
+                    span {
+                        key: "{i}",
+                        class: if *failed { "world-panel-error" } else { "" },
+                        {line.clone()}
+                    }
+                }
+            }
+        }
+    )
+}
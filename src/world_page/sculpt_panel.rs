@@ -0,0 +1,145 @@
+use dioxus::prelude::*;
+use nalgebra::Vector3;
+
+use crate::world_editor::{
+    sculpt::{Brush, BrushKind},
+    World,
+};
+
+fn parse_brush_kind(value: &str, level: isize) -> BrushKind {
+    match value {
+        "lower" => BrushKind::Lower,
+        "smooth" => BrushKind::Smooth,
+        "flatten" => BrushKind::Flatten { level },
+        "erode" => BrushKind::Erode,
+        _ => BrushKind::Raise,
+    }
+}
+
+#[component]
+pub fn SculptPanel(
+    world: Signal<World>,
+    highlighted: Signal<Vec<Vector3<isize>>>,
+    picked_column: Signal<Option<(isize, isize)>>,
+) -> Element {
+    let mut kind = use_signal(|| "raise".to_string());
+    let mut radius = use_signal(|| 4.);
+    let mut strength = use_signal(|| 0.5);
+    let mut level = use_signal(|| 8isize);
+    let mut message: Signal<Option<String>> = use_signal(|| None);
+
+    let brush = Brush {
+        kind: parse_brush_kind(&kind.read(), level()),
+        radius: radius(),
+        strength: strength(),
+    };
+    let preview = picked_column().map(|center| world.read().get_brush_preview(center, &brush));
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "Sculpt" }
+            div { class: "world-panel-row",
+                select {
+                    value: "{kind}",
+                    onchange: move |e| kind.set(e.value()),
+                    option { value: "raise", "Raise" }
+                    option { value: "lower", "Lower" }
+                    option { value: "smooth", "Smooth" }
+                    option { value: "flatten", "Flatten" }
+                    option { value: "erode", "Erode" }
+                }
+                if matches!(brush.kind, BrushKind::Flatten { .. }) {
+                    "Level:"
+                    input {
+                        class: "coordinate-input",
+                        r#type: "number",
+                        value: "{level}",
+                        oninput: move |e| {
+                            if let Ok(new_level) = e.value().parse() {
+                                level.set(new_level);
+                            }
+                        },
+                    }
+                }
+            }
+            div { class: "world-panel-row",
+                {format!("Radius: {}", radius())}
+                input {
+                    r#type: "range",
+                    min: "1",
+                    max: "12",
+                    value: "{radius}",
+                    oninput: move |e| {
+                        if let Ok(new_radius) = e.value().parse() {
+                            radius.set(new_radius);
+                        }
+                    },
+                }
+            }
+            div { class: "world-panel-row",
+                {format!("Strength: {:.0}%", strength() * 100.)}
+                input {
+                    r#type: "range",
+                    min: "0",
+                    max: "1",
+                    step: "0.05",
+                    value: "{strength}",
+                    oninput: move |e| {
+                        if let Ok(new_strength) = e.value().parse() {
+                            strength.set(new_strength);
+                        }
+                    },
+                }
+            }
+            match (picked_column(), &preview) {
+                (Some((x, z)), Some(preview)) => rsx!(
+                    span {
+                        {format!(
+                            "Center: x: {x}, z: {z}. {} columns raised, {} columns lowered.",
+                            preview.iter().filter(|c| c.new_height > c.old_height).count(),
+                            preview.iter().filter(|c| c.new_height < c.old_height).count(),
+                        )}
+                    }
+                    div { class: "world-panel-row",
+                        button {
+                            class: "page-button",
+                            onclick: {
+                                let preview = preview.clone();
+                                move |_| {
+                                    highlighted.set(
+                                        preview
+                                            .iter()
+                                            .map(|c| Vector3::new(c.x, c.new_height - 1, c.z))
+                                            .collect(),
+                                    )
+                                }
+                            },
+                            "Preview on the map"
+                        }
+                        button {
+                            class: "page-button",
+                            disabled: preview.is_empty(),
+                            onclick: move |_| {
+                                let result = world
+                                    .write()
+                                    .edit(&format!("{} brush", brush.kind.get_name()), |world| world.apply_brush((x, z), &brush));
+                                message.set(Some(match result {
+                                    Ok((changed, _)) => format!("{changed} blocks changed."),
+                                    Err(error) => error.get_description(),
+                                }));
+                                highlighted.set(Vec::new());
+                            },
+                            "Apply"
+                        }
+                    }
+                ),
+                _ => rsx!(
+                    span { "Pick the center of the brush on the map." }
+                ),
+            }
+            if let Some(message) = &*message.read() {
+                span { {message.clone()} }
+            }
+        }
+    )
+}
@@ -0,0 +1,107 @@
+use dioxus::prelude::*;
+use nalgebra::Vector3;
+
+use crate::{
+    world_converter::numcraft_v0_1_3::constants::{world::CHUNK_SIZE, BlockType},
+    world_editor::{query::BlockQuery, World},
+};
+
+const MAX_DISPLAYED_RESULTS: usize = 200;
+
+fn parse_query(value: &str) -> Option<BlockQuery> {
+    match value {
+        "highest" => Some(BlockQuery::HighestBlocks),
+        "floating" => Some(BlockQuery::FloatingBlocks),
+        "empty-chunks" => Some(BlockQuery::EmptyChunks),
+        _ => {
+            let id = value.strip_prefix("block-")?.parse().ok()?;
+            Some(BlockQuery::BlockType(BlockType::get_from_id(id)?))
+        }
+    }
+}
+
+/// Convert the result of a query to the block positions to highlight on the map.
+fn get_highlighted_positions(query: BlockQuery, results: &[Vector3<isize>]) -> Vec<Vector3<isize>> {
+    if query != BlockQuery::EmptyChunks {
+        return results.to_vec();
+    }
+
+    let chunk_size = CHUNK_SIZE as isize;
+    results
+        .iter()
+        .flat_map(|chunk_pos| {
+            let origin = chunk_pos * chunk_size;
+            (0..chunk_size)
+                .flat_map(move |z| (0..chunk_size).map(move |x| origin + Vector3::new(x, 0, z)))
+        })
+        .collect()
+}
+
+#[component]
+pub fn SearchPanel(world: Signal<World>, highlighted: Signal<Vec<Vector3<isize>>>) -> Element {
+    let mut selected_query = use_signal(|| "block-7".to_string());
+    let mut results: Signal<Option<(BlockQuery, Vec<Vector3<isize>>)>> = use_signal(|| None);
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "Search" }
+            div { class: "world-panel-row",
+                select {
+                    value: "{selected_query}",
+                    onchange: move |e| selected_query.set(e.value()),
+                    for block_type in (0..=u8::MAX).filter_map(BlockType::get_from_id) {
+                        option { value: "block-{block_type as u8}", {format!("{} blocks", block_type.get_name())} }
+                    }
+                    option { value: "highest", "Highest block of each column" }
+                    option { value: "floating", "Floating blocks" }
+                    option { value: "empty-chunks", "Empty chunks" }
+                }
+                button {
+                    class: "page-button",
+                    onclick: move |_| {
+                        if let Some(query) = parse_query(&selected_query.read()) {
+                            let found = world.read().run_query(query);
+                            highlighted.set(get_highlighted_positions(query, &found));
+                            results.set(Some((query, found)));
+                        }
+                    },
+                    "Search"
+                }
+                button {
+                    class: "page-button",
+                    onclick: move |_| {
+                        highlighted.set(Vec::new());
+                        results.set(None);
+                    },
+                    "Clear"
+                }
+            }
+            if let Some((query, found)) = &*results.read() {
+                span {
+                    if *query == BlockQuery::EmptyChunks {
+                        {format!("{} empty chunks found.", found.len())}
+                    } else {
+                        {format!("{} blocks found.", found.len())}
+                    }
+                }
+                div { class: "search-results",
+                    for pos in found.iter().take(MAX_DISPLAYED_RESULTS).copied() {
+                        a {
+                            key: "{pos.x}-{pos.y}-{pos.z}",
+                            class: "search-result",
+                            title: "Show on the map",
+                            onclick: {
+                                let query = *query;
+                                move |_| highlighted.set(get_highlighted_positions(query, &[pos]))
+                            },
+                            {format!("x: {}, y: {}, z: {}", pos.x, pos.y, pos.z)}
+                        }
+                    }
+                    if found.len() > MAX_DISPLAYED_RESULTS {
+                        span { {format!("... and {} more.", found.len() - MAX_DISPLAYED_RESULTS)} }
+                    }
+                }
+            }
+        }
+    )
+}
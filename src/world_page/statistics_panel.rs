@@ -0,0 +1,138 @@
+use dioxus::prelude::*;
+
+use crate::{world_editor::World, world_page::world_map::get_block_color};
+
+/// Let the browser save a text file.
+fn download_text(file_name: String, text: String, mime_type: &str) {
+    let eval = document::eval(&format!(
+        r#"var name = await dioxus.recv();
+            var text = await dioxus.recv();
+            var blob = new Blob([text], {{ type: "{mime_type}" }});
+            var link = document.createElement("a");
+            link.href = window.URL.createObjectURL(blob);
+            link.download = name;
+            link.click();"#
+    ));
+    let _ = eval.send(file_name);
+    let _ = eval.send(text);
+}
+
+#[component]
+fn StatisticsBar(label: String, value: String, percentage: f32, color: String) -> Element {
+    rsx!(
+        div { class: "statistics-row",
+            span { class: "statistics-label", {label} }
+            div { class: "statistics-bar-area",
+                div {
+                    class: "statistics-bar",
+                    style: "width: {percentage}%; background-color: {color};",
+                }
+            }
+            span { {value} }
+        }
+    )
+}
+
+#[component]
+pub fn StatisticsPanel(world: Signal<World>) -> Element {
+    let statistics = use_memo(move || world.read().get_statistics());
+    let current = statistics.read();
+
+    let file_name = current.world_name.clone();
+    let max_columns = current
+        .height_histogram
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let max_chunk_size = current
+        .chunks
+        .iter()
+        .map(|c| c.compressed_size)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "Statistics" }
+            span {
+                {
+                    format!(
+                        "{} of {} blocks are solid ({:.1}%), {} columns are empty.",
+                        current.solid_block_count,
+                        current.block_count,
+                        current.solid_percentage,
+                        current.empty_columns,
+                    )
+                }
+            }
+            span {
+                {
+                    format!(
+                        "The file takes {} bytes, {:.1} times less than the blocks alone.",
+                        current.file_size,
+                        current.compression_ratio,
+                    )
+                }
+            }
+            div { class: "world-panel-row",
+                button {
+                    class: "page-button",
+                    onclick: {
+                        let file_name = file_name.clone();
+                        move |_| download_text(format!("{file_name}.csv"), statistics.read().to_csv(), "text/csv")
+                    },
+                    "Export CSV"
+                }
+                button {
+                    class: "page-button",
+                    onclick: move |_| download_text(format!("{file_name}.json"), statistics.read().to_json(), "application/json"),
+                    "Export JSON"
+                }
+            }
+            span { class: "statistics-heading", "Blocks" }
+            for block in current.blocks.iter().filter(|b| b.count > 0) {
+                StatisticsBar {
+                    key: "{block.block}",
+                    label: block.block,
+                    value: format!("{} ({:.1}%)", block.count, block.percentage),
+                    percentage: block.percentage,
+                    color: get_block_color(block.block_type),
+                }
+            }
+            span { class: "statistics-heading", "Heights" }
+            div { class: "search-results",
+                for (height, columns) in current.height_histogram.iter().enumerate().rev() {
+                    StatisticsBar {
+                        key: "{height}",
+                        label: format!("y = {height}"),
+                        value: format!("{columns} columns"),
+                        percentage: *columns as f32 * 100. / max_columns as f32,
+                        color: "var(--third-color)",
+                    }
+                }
+            }
+            span { class: "statistics-heading", "Compressed chunks" }
+            div { class: "search-results",
+                for chunk in current.chunks.iter() {
+                    StatisticsBar {
+                        key: "{chunk.pos:?}",
+                        label: format!("{:?}", chunk.pos),
+                        value: format!("{} bytes", chunk.compressed_size),
+                        percentage: chunk.compressed_size as f32 * 100. / max_chunk_size as f32,
+                        color: "var(--second-color)",
+                    }
+                }
+            }
+            span { class: "statistics-heading", "Inventory" }
+            span { {format!("{} of {} slots are used.", current.used_slots, current.slot_count)} }
+            for item in current.items.iter().filter(|i| i.slots > 0) {
+                span { key: "{item.item}",
+                    {format!("{}: {} in {} slots", item.item, item.amount, item.slots)}
+                }
+            }
+        }
+    )
+}
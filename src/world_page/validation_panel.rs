@@ -0,0 +1,72 @@
+use dioxus::prelude::*;
+use nalgebra::Vector3;
+
+use crate::world_editor::{validate::WorldProblem, World};
+
+/// The blocks to show on the map for a problem, if any.
+fn get_problem_positions(problem: &WorldProblem) -> Option<Vec<Vector3<isize>>> {
+    match problem {
+        WorldProblem::BrokenBorder { gaps } => Some(gaps.clone()),
+        WorldProblem::PlayerInsideBlock { block_pos, .. } => Some(vec![*block_pos]),
+        _ => None,
+    }
+}
+
+#[component]
+pub fn ValidationPanel(world: Signal<World>, highlighted: Signal<Vec<Vector3<isize>>>) -> Element {
+    let mut problems: Signal<Option<Vec<WorldProblem>>> = use_signal(|| None);
+    let mut repair_message: Signal<Option<String>> = use_signal(|| None);
+
+    rsx!(
+        div { class: "world-panel",
+            span { class: "world-panel-title", "Validation" }
+            div { class: "world-panel-row",
+                button {
+                    class: "page-button",
+                    onclick: move |_| problems.set(Some(world.read().validate())),
+                    "Check the world"
+                }
+                button {
+                    class: "page-button",
+                    onclick: move |_| {
+                        let report = world.write().edit_state("Repair the border", |world| world.repair_border());
+                        highlighted.set(report.replaced.iter().map(|(pos, _)| *pos).collect());
+                        repair_message.set(Some(report.get_description()));
+                        problems.set(Some(world.read().validate()));
+                    },
+                    "Repair the border"
+                }
+            }
+            label { class: "world-panel-row",
+                input {
+                    r#type: "checkbox",
+                    checked: world.read().enforce_border,
+                    onchange: move |e| world.write().enforce_border = e.checked(),
+                }
+                "Rebuild the border after every edit"
+            }
+            if let Some(message) = &*repair_message.read() {
+                span { {message.clone()} }
+            }
+            if let Some(problems) = &*problems.read() {
+                if problems.is_empty() {
+                    span { "No problem found." }
+                }
+                for (i, problem) in problems.iter().enumerate() {
+                    div {
+                        key: "{i}",
+                        class: if problem.is_warning() { "world-problem world-warning" } else { "world-problem" },
+                        span { {problem.get_description()} }
+                        if let Some(positions) = get_problem_positions(problem) {
+                            a {
+                                class: "search-result",
+                                onclick: move |_| highlighted.set(positions.clone()),
+                                "Show on the map"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    )
+}
@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+
+use dioxus::prelude::*;
+use nalgebra::Vector3;
+
+use crate::{world_converter::numcraft_v0_1_3::constants::BlockType, world_editor::World};
+
+pub fn get_block_color(block_type: BlockType) -> &'static str {
+    &block_type.get_definition().color
+}
+
+/// A top-down view of the world. Each cell shows the highest block of its column.
+/// Clicking a cell sets `picked_column`.
+#[component]
+pub fn WorldMap(
+    world: Signal<World>,
+    highlighted: Signal<Vec<Vector3<isize>>>,
+    picked_column: Signal<Option<(isize, isize)>>,
+) -> Element {
+    let highlighted_columns: HashSet<(isize, isize)> = highlighted
+        .read()
+        .iter()
+        .map(|pos| (pos.x, pos.z))
+        .collect();
+
+    let size = world.read().get_size();
+
+    rsx!(
+        div {
+            class: "world-map",
+            style: "grid-template-columns: repeat({size.x}, 3mm);",
+            for z in 0..size.z {
+                for x in 0..size.x {
+                    {
+                        let highest = world.read().get_highest_block(x, z);
+                        let color = highest
+                            .and_then(|pos| world.read().get_block(pos))
+                            .map_or("transparent", get_block_color);
+                        rsx!(
+                            div {
+                                key: "{x}-{z}",
+                                class: "world-map-cell",
+                                class: if highlighted_columns.contains(&(x, z)) { "world-map-cell-highlighted" } else { "" },
+                                style: "background-color: {color};",
+                                onclick: move |_| picked_column.set(Some((x, z))),
+                                title: match highest {
+                                    Some(pos) => format!("x: {x}, z: {z}, height: {}", pos.y),
+                                    None => format!("x: {x}, z: {z}, empty"),
+                                },
+                            }
+                        )
+                    }
+                }
+            }
+        }
+    )
+}